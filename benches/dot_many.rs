@@ -0,0 +1,29 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mafs::{dot_many, Dvec4, Vec4, Vector};
+
+fn dot_many_vs_loop(c: &mut Criterion) {
+    let vecs: Vec<Dvec4> = (0..1024)
+        .map(|i| {
+            let x = i as f64;
+            Dvec4::new(x.sin(), x.cos(), 0.5 * x, 1.0)
+        })
+        .collect();
+    let against = Dvec4::new(0.3, -1.2, 0.7, 2.0);
+    let mut out = vec![0.0; vecs.len()];
+
+    let mut group = c.benchmark_group("dot_many");
+    group.bench_function("loop", |b| {
+        b.iter(|| {
+            for (v, o) in black_box(&vecs).iter().zip(&mut out) {
+                *o = v.dot(against);
+            }
+        })
+    });
+    group.bench_function("dot_many", |b| {
+        b.iter(|| dot_many(black_box(&vecs), against, &mut out))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, dot_many_vs_loop);
+criterion_main!(benches);
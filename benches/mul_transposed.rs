@@ -0,0 +1,25 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mafs::{Dmat4, Dvec4, Mat4, Vec4};
+
+fn mul_transposed_variants(c: &mut Criterion) {
+    let a = Dmat4::from_rotation_x(0.3).translate(Dvec4::new(1.0, 2.0, 3.0, 0.0));
+    let b = Dmat4::from_scale(Dvec4::new(2.0, 0.5, 1.5, 1.0)).rotate_z(-0.8);
+
+    let mut group = c.benchmark_group("mul_transposed");
+    group.bench_function("mul_transposed", |bencher| {
+        bencher.iter(|| black_box(a).mul_transposed(black_box(b)))
+    });
+    group.bench_function("mul_then_transpose", |bencher| {
+        bencher.iter(|| (black_box(a) * black_box(b)).transpose())
+    });
+    group.bench_function("transposes_then_mul", |bencher| {
+        bencher.iter(|| black_box(b).transpose() * black_box(a).transpose())
+    });
+    group.bench_function("transpose_mul", |bencher| {
+        bencher.iter(|| black_box(b).transpose_mul(black_box(a).transpose()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, mul_transposed_variants);
+criterion_main!(benches);
@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mafs::{Fvec4, Vec4, Vector};
+
+fn norm_fast(c: &mut Criterion) {
+    let vectors: Vec<Fvec4> = (0..1024)
+        .map(|i| {
+            let x = i as f32;
+            Fvec4::new(x.sin(), x.cos(), 0.5 * x, 1.0)
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("norm");
+    group.bench_function("norm", |b| {
+        b.iter(|| black_box(&vectors).iter().map(|v| v.norm()).sum::<f32>())
+    });
+    group.bench_function("norm_fast", |b| {
+        b.iter(|| {
+            black_box(&vectors)
+                .iter()
+                .map(|v| v.norm_fast())
+                .sum::<f32>()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, norm_fast);
+criterion_main!(benches);
@@ -0,0 +1,134 @@
+use crate::Vector;
+use num_traits::float::Float;
+
+/// Catmull-Rom spline through four control points, with `t` in `[0, 1]` going from `p1` to `p2`.
+/// The tangent at `p1` is `(p2 - p0) / 2` and the tangent at `p2` is `(p3 - p1) / 2`, so consecutive
+/// segments join smoothly.
+///
+/// ```
+/// use mafs::{Vec4, Vector, Dvec4, catmull_rom};
+///
+/// let p0 = Dvec4::point(0.0, 0.0, 0.0);
+/// let p1 = Dvec4::point(1.0, 2.0, 0.0);
+/// let p2 = Dvec4::point(3.0, 3.0, 1.0);
+/// let p3 = Dvec4::point(4.0, 1.0, 1.0);
+/// assert_eq!(catmull_rom(p0, p1, p2, p3, 0.0), p1);
+/// assert_eq!(catmull_rom(p0, p1, p2, p3, 1.0), p2);
+///
+/// // Evenly spaced collinear points give a straight line traveled at constant speed
+/// let p = |x| Dvec4::point(x, 2.0 * x, 0.0);
+/// assert_eq!(catmull_rom(p(0.0), p(1.0), p(2.0), p(3.0), 0.25), p(1.25));
+/// ```
+#[inline]
+pub fn catmull_rom<V, S>(p0: V, p1: V, p2: V, p3: V, t: S) -> V
+where
+    V: Vector<S>,
+    S: Float,
+{
+    let half = S::from(0.5).unwrap();
+    let (two, three, four, five) = (
+        S::from(2.0).unwrap(),
+        S::from(3.0).unwrap(),
+        S::from(4.0).unwrap(),
+        S::from(5.0).unwrap(),
+    );
+    let a = (p3 - p0 + (p1 - p2) * three) * half;
+    let b = (p0 * two - p1 * five + p2 * four - p3) * half;
+    let c = (p2 - p0) * half;
+    ((a * t + b) * t + c) * t + p1
+}
+
+/// Cubic Bezier curve with four control points, evaluated with De Casteljau's algorithm.
+/// The curve goes from `p0` at `t == 0` to `p3` at `t == 1`.
+///
+/// ```
+/// use mafs::{Vec4, Vector, Dvec4, bezier3, bezier3_derivative};
+///
+/// let p0 = Dvec4::point(0.0, 0.0, 0.0);
+/// let p1 = Dvec4::point(1.0, 2.0, 0.0);
+/// let p2 = Dvec4::point(3.0, 3.0, 1.0);
+/// let p3 = Dvec4::point(4.0, 1.0, 1.0);
+/// assert_eq!(bezier3(p0, p1, p2, p3, 0.0), p0);
+/// assert_eq!(bezier3(p0, p1, p2, p3, 1.0), p3);
+/// assert_eq!(bezier3_derivative(p0, p1, p2, p3, 0.0), (p1 - p0) * 3.0);
+/// assert_eq!(bezier3_derivative(p0, p1, p2, p3, 1.0), (p3 - p2) * 3.0);
+/// ```
+#[inline]
+pub fn bezier3<V, S>(p0: V, p1: V, p2: V, p3: V, t: S) -> V
+where
+    V: Vector<S>,
+    S: Float,
+{
+    let a = p0.lerp(p1, t);
+    let b = p1.lerp(p2, t);
+    let c = p2.lerp(p3, t);
+    let d = a.lerp(b, t);
+    let e = b.lerp(c, t);
+    d.lerp(e, t)
+}
+
+/// Derivative with respect to `t` of the cubic Bezier curve computed by [`bezier3`].
+#[inline]
+pub fn bezier3_derivative<V, S>(p0: V, p1: V, p2: V, p3: V, t: S) -> V
+where
+    V: Vector<S>,
+    S: Float,
+{
+    let three = S::from(3.0).unwrap();
+    let a = (p1 - p0).lerp(p2 - p1, t);
+    let b = (p2 - p1).lerp(p3 - p2, t);
+    a.lerp(b, t) * three
+}
+
+/// Cubic Hermite spline going from `p0` at `t == 0` to `p1` at `t == 1`, with the tangents `m0` and `m1`
+/// at these endpoints.
+///
+/// ```
+/// use mafs::{Vec4, Vector, Dvec4, hermite, hermite_derivative};
+///
+/// let p0 = Dvec4::point(0.0, 0.0, 0.0);
+/// let m0 = Dvec4::direction(1.0, 2.0, 0.0);
+/// let p1 = Dvec4::point(3.0, 3.0, 1.0);
+/// let m1 = Dvec4::direction(4.0, -1.0, 1.0);
+/// assert_eq!(hermite(p0, m0, p1, m1, 0.0), p0);
+/// assert_eq!(hermite(p0, m0, p1, m1, 1.0), p1);
+/// assert_eq!(hermite_derivative(p0, m0, p1, m1, 0.0), m0);
+/// assert_eq!(hermite_derivative(p0, m0, p1, m1, 1.0), m1);
+/// ```
+#[inline]
+pub fn hermite<V, S>(p0: V, m0: V, p1: V, m1: V, t: S) -> V
+where
+    V: Vector<S>,
+    S: Float,
+{
+    let (one, two, three) = (S::one(), S::from(2.0).unwrap(), S::from(3.0).unwrap());
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let h00 = two * t3 - three * t2 + one;
+    let h10 = t3 - two * t2 + t;
+    let h01 = three * t2 - two * t3;
+    let h11 = t3 - t2;
+    p0 * h00 + m0 * h10 + p1 * h01 + m1 * h11
+}
+
+/// Derivative with respect to `t` of the cubic Hermite spline computed by [`hermite`].
+#[inline]
+pub fn hermite_derivative<V, S>(p0: V, m0: V, p1: V, m1: V, t: S) -> V
+where
+    V: Vector<S>,
+    S: Float,
+{
+    let (one, two, three, four, six) = (
+        S::one(),
+        S::from(2.0).unwrap(),
+        S::from(3.0).unwrap(),
+        S::from(4.0).unwrap(),
+        S::from(6.0).unwrap(),
+    );
+    let t2 = t * t;
+    let h00 = six * t2 - six * t;
+    let h10 = three * t2 - four * t + one;
+    let h01 = six * t - six * t2;
+    let h11 = three * t2 - two * t;
+    p0 * h00 + m0 * h10 + p1 * h01 + m1 * h11
+}
@@ -1,4 +1,4 @@
-use crate::{Dvec4, Mat4};
+use crate::{Dvec2, Dvec4, LengthError, Mat4, Vec2, Vec4, Vector};
 use std::arch::x86_64::*;
 
 /// 4x4 matrix with double precision
@@ -156,4 +156,678 @@ impl Mat4<f64, Dvec4> for Dmat4 {
     }
 }
 
+impl Dmat4 {
+    /// Treat this matrix as a 2D affine or projective transformation and apply it to a point.
+    /// Only the rows and columns 0, 1 and 3 are used: the point is extended to `(x, y, 0, 1)`,
+    /// transformed, then divided by `w`.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec2, Dvec2, Vec4, Dvec4};
+    ///
+    /// let (s, c) = (0.5f64.sin(), 0.5f64.cos());
+    /// let m = Dmat4::from_columns(
+    ///     Dvec4::new(c, s, 0.0, 0.0),
+    ///     Dvec4::new(-s, c, 0.0, 0.0),
+    ///     Dvec4::new(0.0, 0.0, 1.0, 0.0),
+    ///     Dvec4::new(3.0, 4.0, 0.0, 1.0),
+    /// );
+    /// assert_eq!(m.transform_point2(Dvec2::new(1.0, 0.0)), Dvec2::new(c + 3.0, s + 4.0));
+    /// assert_eq!(m.transform_vector2(Dvec2::new(1.0, 0.0)), Dvec2::new(c, s));
+    /// ```
+    pub fn transform_point2(&self, p: Dvec2) -> Dvec2 {
+        let v = self.mul_vector(Dvec4::new(p[0], p[1], 0.0, 1.0));
+        Dvec2::new(v[0] / v[3], v[1] / v[3])
+    }
+
+    /// Same as [`Dmat4::transform_point2`], but for a direction: the vector is extended to `(x, y, 0, 0)`,
+    /// so the translation is ignored and no division is performed.
+    pub fn transform_vector2(&self, v: Dvec2) -> Dvec2 {
+        let v = self.mul_vector(Dvec4::new(v[0], v[1], 0.0, 0.0));
+        Dvec2::new(v[0], v[1])
+    }
+
+    /// Create a matrix from a slice of exactly 16 elements in column-major order.
+    /// Unlike [`Mat4::from_elements`], this does not panic when the length is wrong.
+    pub fn try_from_vec(v: &[f64]) -> Result<Dmat4, LengthError> {
+        if v.len() == 16 {
+            Ok(Dmat4::from_elements(v.iter().copied()))
+        } else {
+            Err(LengthError {
+                expected: 16,
+                actual: v.len(),
+            })
+        }
+    }
+
+    /// Copy the components into a `Vec`, in column-major order.
+    pub fn into_vec(&self) -> Vec<f64> {
+        self.elements().collect()
+    }
+
+    /// Weighted sum of some of the `matrices`: `weights[0] * matrices[indices[0]] + weights[1] * ...`,
+    /// e.g. to blend the bone matrices of a vertex for skinning.
+    /// Each step is accumulated with a fused multiply-add. The weights do not need to sum to 1.
+    ///
+    /// Panics if `indices` and `weights` do not have the same length, or if an index is out of bounds.
+    pub fn blend(matrices: &[Dmat4], indices: &[usize], weights: &[f64]) -> Dmat4 {
+        assert_eq!(indices.len(), weights.len());
+        unsafe {
+            let mut result = [_mm256_setzero_pd(); 4];
+            for (&i, &w) in indices.iter().zip(weights) {
+                let w = _mm256_set1_pd(w);
+                for (r, c) in result.iter_mut().zip(&matrices[i].inner) {
+                    *r = _mm256_fmadd_pd(w, c.inner, *r);
+                }
+            }
+            Dmat4 {
+                inner: result.map(|inner| Dvec4 { inner }),
+            }
+        }
+    }
+}
+
 implement_matops!(Dmat4, Dvec4, f64);
+
+/// Approximate the Jacobian matrix of `f` at `at` with central differences of step `h`.
+/// Column `i` of the result is the partial derivative of `f` with respect to component `i`.
+///
+/// The truncation error decreases with `h` but the rounding error increases as `h` gets smaller.
+/// For a smooth function, `h` around `1e-5` times the magnitude of `at` is usually a good compromise.
+pub fn numerical_jacobian<F: Fn(Dvec4) -> Dvec4>(f: F, at: Dvec4, h: f64) -> Dmat4 {
+    let mut result = Dmat4::default();
+    for i in 0..4 {
+        let mut step = Dvec4::splat(0.0);
+        step[i] = h;
+        result[i] = (f(at + step) - f(at - step)) / (2.0 * h);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClipSpace;
+
+    #[test]
+    fn try_inverse_se3_works() {
+        let m = Dmat4::from_columns(
+            Dvec4::new(
+                0.6666666666666666,
+                0.6666666666666666,
+                -0.3333333333333333,
+                0.0,
+            ),
+            Dvec4::new(
+                -0.3333333333333333,
+                0.6666666666666666,
+                0.6666666666666666,
+                0.0,
+            ),
+            Dvec4::new(
+                0.6666666666666666,
+                -0.3333333333333333,
+                0.6666666666666666,
+                0.0,
+            ),
+            Dvec4::new(-4.0, 5.0, 6.0, 1.0),
+        );
+        let inv = m.try_inverse_se3(1e-12).unwrap();
+        assert!((m * inv).abs_diff_eq(Dmat4::identity(), 1e-12));
+        assert!((inv * m).abs_diff_eq(Dmat4::identity(), 1e-12));
+
+        let scale = Dmat4::from_columns(
+            Dvec4::new(2.0, 0.0, 0.0, 0.0),
+            Dvec4::new(0.0, 2.0, 0.0, 0.0),
+            Dvec4::new(0.0, 0.0, 2.0, 0.0),
+            Dvec4::new(0.0, 0.0, 0.0, 1.0),
+        );
+        assert!(scale.try_inverse_se3(1e-12).is_none());
+    }
+
+    #[test]
+    fn transpose_mul_works() {
+        let a = Dmat4::from_columns(
+            Dvec4::new(1.0, 2.0, 3.0, 4.0),
+            Dvec4::new(5.0, 6.0, 7.0, 8.0),
+            Dvec4::new(9.0, 10.0, 11.0, 12.0),
+            Dvec4::new(13.0, 14.0, 15.0, 16.0),
+        );
+        let b = Dmat4::from_columns(
+            Dvec4::new(17.0, -18.0, 19.0, 20.0),
+            Dvec4::new(21.0, 22.0, -23.0, 24.0),
+            Dvec4::new(25.0, 26.0, 27.0, -28.0),
+            Dvec4::new(-29.0, 30.0, 31.0, 32.0),
+        );
+        assert_eq!(a.transpose_mul(b), a.transpose() * b);
+
+        let g = b.gram();
+        assert_eq!(g, g.transpose());
+        assert_eq!(g, b.transpose() * b);
+    }
+
+    #[test]
+    fn extract_scale_works() {
+        let (c, s) = (0.3f64.cos(), 0.3f64.sin());
+        let rotation = Dmat4::from_columns(
+            Dvec4::new(c, s, 0.0, 0.0),
+            Dvec4::new(-s, c, 0.0, 0.0),
+            Dvec4::new(0.0, 0.0, 1.0, 0.0),
+            Dvec4::new(1.0, 2.0, 3.0, 1.0),
+        );
+        let scale = Dmat4::from_columns(
+            Dvec4::new(2.0, 0.0, 0.0, 0.0),
+            Dvec4::new(0.0, 3.0, 0.0, 0.0),
+            Dvec4::new(0.0, 0.0, 4.0, 0.0),
+            Dvec4::new(0.0, 0.0, 0.0, 1.0),
+        );
+        let m = rotation * scale;
+        assert!(
+            m.extract_scale()
+                .max_abs_diff(Dvec4::new(2.0, 3.0, 4.0, 0.0))
+                < 1e-12
+        );
+
+        let reflection = Dmat4::from_columns(
+            Dvec4::new(1.0, 0.0, 0.0, 0.0),
+            Dvec4::new(0.0, -1.0, 0.0, 0.0),
+            Dvec4::new(0.0, 0.0, 1.0, 0.0),
+            Dvec4::new(0.0, 0.0, 0.0, 1.0),
+        );
+        assert_eq!(reflection.extract_scale(), Dvec4::new(-1.0, 1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn billboard_works() {
+        let position = Dvec4::point(1.0, 2.0, 3.0);
+        let camera_pos = Dvec4::point(4.0, 6.0, 3.0);
+        let up = Dvec4::direction(0.0, 0.0, 1.0);
+        let m = Dmat4::billboard(position, camera_pos, up);
+
+        assert!(m[2].max_abs_diff(Dvec4::direction(0.6, 0.8, 0.0)) < 1e-12);
+        assert!(m.is_se3(1e-12));
+        assert_eq!(m[3], position);
+        assert!(m[1].max_abs_diff(up) < 1e-12);
+    }
+
+    #[test]
+    fn iterators_work() {
+        let m = Dmat4::from_columns(
+            Dvec4::new(1.0, 2.0, 3.0, 4.0),
+            Dvec4::new(5.0, 6.0, 7.0, 8.0),
+            Dvec4::new(9.0, 10.0, 11.0, 12.0),
+            Dvec4::new(13.0, 14.0, 15.0, 16.0),
+        );
+        assert_eq!(m.elements().sum::<f64>(), 136.0);
+        assert!(m.elements().eq((1..=16).map(|x| x as f64)));
+        assert!(m.columns().eq(m.as_array().iter().copied()));
+        assert_eq!(Dmat4::from_elements(m.elements()), m);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_elements_too_short() {
+        Dmat4::from_elements([1.0; 15]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_elements_too_long() {
+        Dmat4::from_elements([1.0; 17]);
+    }
+
+    #[test]
+    fn transform_aabb_works() {
+        let (c, s) = (0.7f64.cos(), 0.7f64.sin());
+        let rotation_x = Dmat4::from_columns(
+            Dvec4::new(1.0, 0.0, 0.0, 0.0),
+            Dvec4::new(0.0, c, s, 0.0),
+            Dvec4::new(0.0, -s, c, 0.0),
+            Dvec4::new(0.0, 0.0, 0.0, 1.0),
+        );
+        let rotation_z = Dmat4::from_columns(
+            Dvec4::new(c, s, 0.0, 0.0),
+            Dvec4::new(-s, c, 0.0, 0.0),
+            Dvec4::new(0.0, 0.0, 1.0, 0.0),
+            Dvec4::new(1.0, -2.0, 3.0, 1.0),
+        );
+        let m = rotation_z * rotation_x;
+        let min = Dvec4::point(-1.0, 0.0, 2.0);
+        let max = Dvec4::point(3.0, 1.0, 5.0);
+
+        let mut expected_min = Dvec4::splat(f64::INFINITY);
+        let mut expected_max = Dvec4::splat(f64::NEG_INFINITY);
+        for i in 0..8 {
+            let corner = Dvec4::point(
+                if i & 1 == 0 { min[0] } else { max[0] },
+                if i & 2 == 0 { min[1] } else { max[1] },
+                if i & 4 == 0 { min[2] } else { max[2] },
+            );
+            expected_min = expected_min.min_componentwise(m * corner);
+            expected_max = expected_max.max_componentwise(m * corner);
+        }
+
+        let (result_min, result_max) = m.transform_aabb(min, max);
+        assert!(result_min.max_abs_diff(expected_min) < 1e-12);
+        assert!(result_max.max_abs_diff(expected_max) < 1e-12);
+    }
+
+    #[test]
+    fn interpolate_trs_works() {
+        let (s, c) = (
+            std::f64::consts::FRAC_PI_4.sin(),
+            std::f64::consts::FRAC_PI_4.cos(),
+        );
+        let a = Dmat4::identity();
+        let b = Dmat4::from_scale_rotation_translation(
+            Dvec4::splat(3.0),
+            Dvec4::new(0.0, 0.0, s, c),
+            Dvec4::new(2.0, 4.0, 6.0, 0.0),
+        );
+        assert!(b[0].max_abs_diff(Dvec4::new(0.0, 3.0, 0.0, 0.0)) < 1e-12);
+
+        let m = a.interpolate_trs(b, 0.5);
+        let (scale, rotation, translation) = m.decompose();
+        assert!(scale.max_abs_diff(Dvec4::new(2.0, 2.0, 2.0, 0.0)) < 1e-12);
+        assert!(translation.max_abs_diff(Dvec4::new(1.0, 2.0, 3.0, 0.0)) < 1e-12);
+        assert!((rotation.norm() - 1.0).abs() < 1e-12);
+
+        let rigid =
+            Dmat4::from_scale_rotation_translation(Dvec4::splat(1.0), rotation, translation);
+        assert!(rigid.is_se3(1e-12));
+        let angle = std::f64::consts::FRAC_PI_4;
+        assert!(rigid[0].max_abs_diff(Dvec4::new(angle.cos(), angle.sin(), 0.0, 0.0)) < 1e-12);
+
+        assert!(a.interpolate_trs(b, 0.0).abs_diff_eq(a, 1e-12));
+        assert!(a.interpolate_trs(b, 1.0).abs_diff_eq(b, 1e-12));
+    }
+
+    #[test]
+    fn numerical_jacobian_works() {
+        let m = Dmat4::from_columns(
+            Dvec4::new(1.0, 2.0, 3.0, 4.0),
+            Dvec4::new(5.0, -6.0, 7.0, 8.0),
+            Dvec4::new(9.0, 10.0, -11.0, 12.0),
+            Dvec4::new(13.0, 14.0, 15.0, 16.0),
+        );
+        let at = Dvec4::new(0.5, -1.0, 2.0, 3.0);
+        let jacobian = numerical_jacobian(|x| m * x, at, 1e-3);
+        assert!(jacobian.abs_diff_eq(m, 1e-9));
+
+        let f = |v: Dvec4| Dvec4::new(v[0] * v[1], v[2].sin(), v[3] * v[3], v[0]);
+        let expected = Dmat4::from_rows(
+            [at[1], at[0], 0.0, 0.0],
+            [0.0, 0.0, at[2].cos(), 0.0],
+            [0.0, 0.0, 0.0, 2.0 * at[3]],
+            [1.0, 0.0, 0.0, 0.0],
+        );
+        let jacobian = numerical_jacobian(f, at, 1e-5);
+        assert!(jacobian.abs_diff_eq(expected, 1e-8));
+    }
+
+    #[test]
+    fn solve_works() {
+        let m = Dmat4::from_columns(
+            Dvec4::new(4.0, -2.0, 1.0, 3.0),
+            Dvec4::new(1.0, 5.0, -3.0, 2.0),
+            Dvec4::new(0.0, 2.0, 6.0, -1.0),
+            Dvec4::new(2.0, 1.0, 1.0, 7.0),
+        );
+        let x = Dvec4::new(1.0, 2.0, -3.0, 4.0);
+        let solution = m.solve(m * x).unwrap();
+        assert!(solution.max_abs_diff(x) < 1e-12);
+        let inverse = m.inverse().unwrap();
+        assert!((m * inverse).abs_diff_eq(Dmat4::identity(), 1e-12));
+
+        let mut singular = m;
+        singular[3] = singular[0] * 2.0 - singular[1];
+        assert!(singular.solve(x).is_none());
+        assert!(singular.inverse().is_none());
+
+        // Hilbert matrix, badly conditioned
+        let hilbert = Dmat4::from_elements((0..16).map(|k| 1.0 / ((k / 4 + k % 4) as f64 + 1.0)));
+        let x = Dvec4::new(1.0, -1.0, 1.0, -1.0);
+        let b = hilbert * x;
+        let solve_error = hilbert.solve(b).unwrap().max_abs_diff(x);
+        // Inverse computed independently of solve, with the adjugate formula
+        let determinant = hilbert.determinant();
+        let mut adjugate_inverse = Dmat4::splat(0.0);
+        for col in 0..4 {
+            for row in 0..4 {
+                adjugate_inverse[col][row] = hilbert.cofactor(col, row) / determinant;
+            }
+        }
+        let inverse_error = (adjugate_inverse * b).max_abs_diff(x);
+        assert!(solve_error < 1e-9);
+        assert!(solve_error <= inverse_error);
+        let inverse = hilbert.inverse().unwrap();
+        assert!(inverse.abs_diff_eq(adjugate_inverse, 1e-6));
+    }
+
+    #[test]
+    fn from_quat_translation_works() {
+        // Rotation of 120 degrees around (1, 1, 1), which permutes the axes
+        let rotation = Dvec4::splat(0.5);
+        let translation = Dvec4::new(1.0, 2.0, 3.0, 0.0);
+        let m = Dmat4::from_quat_translation(rotation, translation);
+
+        assert!(m.is_se3(1e-12));
+        assert_eq!(m * Dvec4::point(0.0, 0.0, 0.0), Dvec4::point(1.0, 2.0, 3.0));
+        assert!(
+            (m * Dvec4::direction(1.0, 0.0, 0.0)).max_abs_diff(Dvec4::direction(0.0, 1.0, 0.0))
+                < 1e-12
+        );
+        assert!(
+            (m * Dvec4::point(0.0, 1.0, 0.0)).max_abs_diff(Dvec4::point(1.0, 2.0, 4.0)) < 1e-12
+        );
+        assert!((m.inverse_se3() * m).abs_diff_eq(Dmat4::identity(), 1e-12));
+    }
+
+    #[test]
+    fn transpose_in_place_works() {
+        let original = Dmat4::from_columns(
+            Dvec4::new(1.0, 2.0, 3.0, 4.0),
+            Dvec4::new(5.0, 6.0, 7.0, 8.0),
+            Dvec4::new(9.0, 10.0, 11.0, 12.0),
+            Dvec4::new(13.0, 14.0, 15.0, 16.0),
+        );
+        let mut m = original;
+        m.transpose_in_place();
+        assert_eq!(m, original.transpose());
+        m.transpose_in_place();
+        assert_eq!(m, original);
+    }
+
+    #[test]
+    fn projection_depth_ranges() {
+        let (near, far) = (0.5, 100.0);
+        let expected = [
+            (ClipSpace::NegativeOneToOne, -1.0, 1.0),
+            (ClipSpace::ZeroToOne, 0.0, 1.0),
+            (ClipSpace::ReversedZ, 1.0, 0.0),
+        ];
+        for (clip, near_z, far_z) in expected {
+            let depth = |m: Dmat4, z: f64| {
+                let v = m * Dvec4::point(0.3, -0.2, z);
+                v[2] / v[3]
+            };
+            let perspective = Dmat4::perspective(1.2, 16.0 / 9.0, near, far, clip);
+            assert!((depth(perspective, -near) - near_z).abs() < 1e-12);
+            assert!((depth(perspective, -far) - far_z).abs() < 1e-12);
+
+            let orthographic = Dmat4::orthographic(-4.0, 4.0, -3.0, 3.0, near, far, clip);
+            assert!((depth(orthographic, -near) - near_z).abs() < 1e-12);
+            assert!((depth(orthographic, -far) - far_z).abs() < 1e-12);
+            let corner = orthographic * Dvec4::point(4.0, 3.0, -near);
+            assert!(corner.max_abs_diff(Dvec4::point(1.0, 1.0, near_z)) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn try_from_vec_works() {
+        let m = Dmat4::from_elements((1..=16).map(|x| x as f64));
+        assert_eq!(m.into_vec(), (1..=16).map(|x| x as f64).collect::<Vec<_>>());
+        assert_eq!(Dmat4::try_from_vec(&m.into_vec()), Ok(m));
+        let error = Dmat4::try_from_vec(&[1.0; 15]).unwrap_err();
+        assert_eq!((error.expected, error.actual), (16, 15));
+        let error = Dmat4::try_from_vec(&[1.0; 17]).unwrap_err();
+        assert_eq!((error.expected, error.actual), (16, 17));
+        assert_eq!(error.to_string(), "expected 16 elements, got 17");
+    }
+
+    #[test]
+    fn left_handed_is_mirrored() {
+        // Mirroring the world along z turns a right-handed setup into a left-handed one
+        let flip_z = Dmat4::from_scale(Dvec4::new(1.0, 1.0, -1.0, 1.0));
+        let eye = Dvec4::point(1.0, 2.0, 3.0);
+        let target = Dvec4::point(-2.0, 0.5, 7.0);
+        let up = Dvec4::direction(0.1, 1.0, 0.2);
+
+        let rh = Dmat4::look_at(flip_z * eye, flip_z * target, flip_z * up);
+        let lh = Dmat4::look_at_lh(eye, target, up);
+        assert!(lh.abs_diff_eq(flip_z * rh * flip_z, 1e-12));
+        assert!(lh.is_se3(1e-12));
+        assert!((lh * target).max_abs_diff(Dvec4::point(0.0, 0.0, (target - eye).norm())) < 1e-12);
+
+        let rh = Dmat4::perspective(1.0, 1.5, 0.1, 50.0, ClipSpace::ZeroToOne);
+        let lh = Dmat4::perspective_lh(1.0, 1.5, 0.1, 50.0, ClipSpace::ZeroToOne);
+        assert_eq!(lh, rh * flip_z);
+        let near = lh * Dvec4::point(0.0, 0.0, 0.1);
+        assert!((near[2] / near[3]).abs() < 1e-12);
+
+        let rh = Dmat4::orthographic(-1.0, 1.0, -2.0, 2.0, 0.1, 50.0, ClipSpace::NegativeOneToOne);
+        let lh =
+            Dmat4::orthographic_lh(-1.0, 1.0, -2.0, 2.0, 0.1, 50.0, ClipSpace::NegativeOneToOne);
+        assert_eq!(lh, rh * flip_z);
+    }
+
+    #[test]
+    fn symmetric_eigen_3x3_works() {
+        let diagonal = Dmat4::from_scale(Dvec4::new(3.0, -1.0, 2.0, 1.0));
+        let (values, vectors) = diagonal.symmetric_eigen_3x3();
+        assert_eq!(values, Dvec4::new(3.0, -1.0, 2.0, 0.0));
+        assert_eq!(vectors, Dmat4::identity());
+
+        let matrices = [
+            Dmat4::from_rows(
+                [2.0, 1.0, 0.0, 0.0],
+                [1.0, 2.0, 0.0, 0.0],
+                [0.0, 0.0, 3.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ),
+            Dmat4::from_rows(
+                [4.0, -2.0, 0.5, 0.0],
+                [-2.0, 1.0, 3.0, 0.0],
+                [0.5, 3.0, -5.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ),
+        ];
+        for m in matrices {
+            let (values, vectors) = m.symmetric_eigen_3x3();
+            assert!(vectors.is_se3(1e-12));
+            for i in 0..3 {
+                let v = vectors[i];
+                assert!((m * v).max_abs_diff(v * values[i]) < 1e-12);
+            }
+        }
+        let (values, _) = matrices[0].symmetric_eigen_3x3();
+        let mut sorted = [values[0], values[1], values[2]];
+        sorted.sort_by(f64::total_cmp);
+        assert!((sorted[0] - 1.0).abs() < 1e-12);
+        assert!((sorted[1] - 3.0).abs() < 1e-12);
+        assert!((sorted[2] - 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn mul_projection_works() {
+        let view = Dmat4::look_at(
+            Dvec4::point(1.0, 2.0, 3.0),
+            Dvec4::point(-2.0, 0.5, 7.0),
+            Dvec4::direction(0.0, 1.0, 0.0),
+        );
+        let projections = [
+            Dmat4::perspective(1.0, 1.5, 0.1, 50.0, ClipSpace::ReversedZ),
+            Dmat4::perspective_lh(1.0, 1.5, 0.1, 50.0, ClipSpace::NegativeOneToOne),
+            Dmat4::orthographic(-1.0, 2.0, -2.0, 3.0, 0.1, 50.0, ClipSpace::ZeroToOne),
+        ];
+        for projection in projections {
+            let expected = projection.mul_matrix(view);
+            assert!(projection.mul_projection(view).abs_diff_eq(expected, 1e-12));
+        }
+    }
+
+    #[test]
+    fn dominant_eigenvector_works() {
+        let rotation = Dmat4::from_rotation_z(0.4) * Dmat4::from_rotation_x(1.1);
+        let eigenvalues = Dmat4::from_scale(Dvec4::new(2.0, -10.0, 1.0, 1.0));
+        let m = rotation * eigenvalues * rotation.transpose();
+
+        let v = m.dominant_eigenvector(50);
+        assert_eq!(v[3], 0.0);
+        assert!((v.dot3(rotation[1]).abs() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn householder_works() {
+        let v = Dvec4::new(1.0, -2.0, 0.5, 3.0);
+        let h = Dmat4::householder(v);
+        assert!(h.is_symmetric(1e-12));
+        assert!((h.transpose() * h).abs_diff_eq(Dmat4::identity(), 1e-12));
+        assert!((h.determinant() + 1.0).abs() < 1e-12);
+        assert!((h * v).max_abs_diff(-v) < 1e-12);
+
+        let perpendicular = Dvec4::new(2.0, 1.0, 0.0, 0.0);
+        assert_eq!(perpendicular.dot(v), 0.0);
+        assert!((h * perpendicular).max_abs_diff(perpendicular) < 1e-12);
+    }
+
+    #[test]
+    fn determinant_works() {
+        let m = Dmat4::from_rows(
+            [2.0, 1.0, 0.0, 3.0],
+            [1.0, 3.0, 1.0, 0.0],
+            [0.0, 1.0, 4.0, 1.0],
+            [5.0, 0.0, 1.0, 5.0],
+        );
+        assert_eq!(m.determinant(), -88.0);
+        assert_eq!(m.transpose().determinant(), -88.0);
+        assert_eq!(Dmat4::from_scale(Dvec4::splat(2.0)).determinant(), 8.0);
+    }
+
+    #[test]
+    fn closest_rotation_works() {
+        let rotation = Dmat4::from_rotation_z(0.4) * Dmat4::from_rotation_x(1.1);
+        let noise = Dmat4::from_rows(
+            [0.01, -0.02, 0.015, 0.0],
+            [0.005, 0.01, -0.01, 0.0],
+            [-0.02, 0.01, 0.02, 0.0],
+            [0.0, 0.0, 0.0, 0.0],
+        );
+        let perturbed = rotation + noise;
+
+        let closest = perturbed.closest_rotation();
+        assert!(closest.is_se3(1e-12));
+        assert!(rotation.closest_rotation().abs_diff_eq(rotation, 1e-12));
+
+        // Gram-Schmidt favors the first column
+        let x = perturbed[0].normalize();
+        let y = (perturbed[1] - x * x.dot(perturbed[1])).normalize();
+        let gram_schmidt = Dmat4::from_columns(x, y, x.cross(y), perturbed[3]);
+        assert!(gram_schmidt.is_se3(1e-12));
+        assert!(
+            (closest - perturbed).frobenius_norm() < (gram_schmidt - perturbed).frobenius_norm()
+        );
+    }
+
+    #[test]
+    fn blend_works() {
+        let a = Dmat4::from_translation(Dvec4::new(2.0, 0.0, 0.0, 0.0));
+        let b = Dmat4::from_scale(Dvec4::new(3.0, 3.0, 3.0, 1.0));
+        let matrices = [a, b];
+        assert_eq!(Dmat4::blend(&matrices, &[0, 1], &[0.5, 0.5]), (a + b) * 0.5);
+        assert_eq!(Dmat4::blend(&matrices, &[], &[]), Dmat4::default());
+
+        let bones = [
+            Dmat4::from_rotation_x(0.3),
+            Dmat4::from_rotation_y(-0.7),
+            Dmat4::from_translation(Dvec4::new(1.0, 2.0, 3.0, 0.0)),
+            Dmat4::from_scale(Dvec4::new(2.0, 0.5, 1.0, 1.0)),
+            Dmat4::identity(),
+        ];
+        let indices = [4, 0, 2, 3];
+        let weights = [0.1, 0.2, 0.3, 0.4];
+        let expected = bones[4] * 0.1 + bones[0] * 0.2 + bones[2] * 0.3 + bones[3] * 0.4;
+        let blended = Dmat4::blend(&bones, &indices, &weights);
+        assert!(blended.abs_diff_eq(expected, 1e-15));
+    }
+
+    #[test]
+    fn frustum_works() {
+        let (fov_y, aspect, near, far) = (1.2, 1.5, 0.5, 20.0);
+        let top = near * (fov_y / 2.0f64).tan();
+        let right = top * aspect;
+        for clip in [
+            ClipSpace::NegativeOneToOne,
+            ClipSpace::ZeroToOne,
+            ClipSpace::ReversedZ,
+        ] {
+            let frustum = Dmat4::frustum(-right, right, -top, top, near, far, clip);
+            let perspective = Dmat4::perspective(fov_y, aspect, near, far, clip);
+            assert!(frustum.abs_diff_eq(perspective, 1e-12));
+        }
+
+        let m = Dmat4::frustum(-1.0, 3.0, -2.0, 0.5, 2.0, 10.0, ClipSpace::NegativeOneToOne);
+        let corners = [
+            (
+                Dvec4::point(-1.0, -2.0, -2.0),
+                Dvec4::point(-1.0, -1.0, -1.0),
+            ),
+            (Dvec4::point(3.0, -2.0, -2.0), Dvec4::point(1.0, -1.0, -1.0)),
+            (Dvec4::point(-1.0, 0.5, -2.0), Dvec4::point(-1.0, 1.0, -1.0)),
+            (Dvec4::point(3.0, 0.5, -2.0), Dvec4::point(1.0, 1.0, -1.0)),
+        ];
+        for (corner, expected) in corners {
+            let clip = m * corner;
+            assert!((clip / clip[3]).max_abs_diff(expected) < 1e-12);
+        }
+        let far = m * Dvec4::point(15.0, 2.5, -10.0);
+        assert!((far / far[3]).max_abs_diff(Dvec4::point(1.0, 1.0, 1.0)) < 1e-12);
+    }
+
+    #[test]
+    fn pretty_works() {
+        assert_eq!(
+            Dmat4::identity().pretty(),
+            "1.0000  0.0000  0.0000  0.0000\n\
+             0.0000  1.0000  0.0000  0.0000\n\
+             0.0000  0.0000  1.0000  0.0000\n\
+             0.0000  0.0000  0.0000  1.0000"
+        );
+        assert_eq!(format!("{:?}", Dmat4::identity()), "[[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0]]");
+    }
+
+    #[test]
+    fn frustum_corners_works() {
+        let eye = Dvec4::point(1.0, 2.0, 3.0);
+        let target = Dvec4::point(0.0, 0.0, 0.0);
+        let view = Dmat4::look_at(eye, target, Dvec4::direction(0.0, 1.0, 0.0));
+        let forward = (target - eye).normalize();
+        let (near, far) = (0.5, 20.0);
+        for clip in [
+            ClipSpace::NegativeOneToOne,
+            ClipSpace::ZeroToOne,
+            ClipSpace::ReversedZ,
+        ] {
+            let projection = Dmat4::perspective(1.0, 1.5, near, far, clip);
+            let corners = (projection * view).frustum_corners(clip).unwrap();
+            for (i, corner) in corners.into_iter().enumerate() {
+                assert_eq!(corner[3], 1.0);
+                let depth = (corner - eye).dot(forward);
+                let expected = if i < 4 { near } else { far };
+                assert!((depth - expected).abs() < 1e-9 * far);
+            }
+            for i in 0..4 {
+                assert!((corners[i] - eye).norm() < (corners[i + 4] - eye).norm());
+            }
+            // The right corners are further along the right axis of the camera than the left ones
+            let right = forward.cross(Dvec4::direction(0.0, 1.0, 0.0));
+            assert!(corners[1].dot3(right) > corners[0].dot3(right));
+        }
+        assert!(Dmat4::default()
+            .frustum_corners(ClipSpace::ZeroToOne)
+            .is_none());
+    }
+
+    #[test]
+    fn mul_transposed_works() {
+        let a = Dmat4::from_rows(
+            [1.0, -2.0, 3.0, 0.5],
+            [0.0, 4.0, -1.0, 2.0],
+            [7.0, 0.0, 1.0, -3.0],
+            [0.0, 0.0, 0.0, 1.0],
+        );
+        let b = Dmat4::from_rotation_y(0.4).translate(Dvec4::new(1.0, -2.0, 3.0, 0.0));
+        assert_eq!(a.mul_transposed(b), (a * b).transpose());
+        assert_eq!(b.mul_transposed(a), (b * a).transpose());
+        assert_eq!(a.mul_transposed(Dmat4::identity()), a.transpose());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Vec2;
+use crate::{ulps_eq_f64, Vec2, Vector};
 use std::arch::x86_64::*;
 
 /// 2D vector with double precision
@@ -8,7 +8,7 @@ use std::arch::x86_64::*;
 /// ## Examples
 ///
 /// ```
-/// use mafs::{Vec2, Dvec2};
+/// use mafs::{Vec2, Vector, Dvec2};
 ///
 /// // Construction
 /// let a = Dvec2::new(2.0, 3.0);
@@ -51,25 +51,24 @@ impl std::fmt::Debug for Dvec2 {
     }
 }
 
-impl Vec2<f64> for Dvec2 {
+impl Vector<f64> for Dvec2 {
     #[inline]
-    fn new(x: f64, y: f64) -> Dvec2 {
+    fn splat(value: f64) -> Dvec2 {
         unsafe {
-            // The order is reversed!
             Dvec2 {
-                inner: _mm_set_pd(y, x),
+                inner: _mm_set1_pd(value),
             }
         }
     }
 
     #[inline]
-    fn as_array(&self) -> &[f64; 2] {
-        unsafe { &*(self as *const Dvec2 as *const [f64; 2]) }
+    fn as_slice(&self) -> &[f64] {
+        self.as_array()
     }
 
     #[inline]
-    fn as_mut_array(&mut self) -> &mut [f64; 2] {
-        unsafe { &mut *(self as *mut Dvec2 as *mut [f64; 2]) }
+    fn as_mut_slice(&mut self) -> &mut [f64] {
+        self.as_mut_array()
     }
 
     #[inline]
@@ -135,6 +134,72 @@ impl Vec2<f64> for Dvec2 {
         }
     }
 
+    #[inline]
+    fn round(&self) -> Dvec2 {
+        unsafe {
+            Dvec2 {
+                inner: _mm_round_pd::<{ _MM_FROUND_TO_NEAREST_INT | _MM_FROUND_NO_EXC }>(
+                    self.inner,
+                ),
+            }
+        }
+    }
+
+    #[inline]
+    fn abs(&self) -> Dvec2 {
+        unsafe {
+            Dvec2 {
+                inner: _mm_andnot_pd(_mm_set1_pd(-0.0), self.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn signum_nonzero(&self) -> Dvec2 {
+        unsafe {
+            let negative = _mm_cmplt_pd(self.inner, _mm_setzero_pd());
+            Dvec2 {
+                inner: _mm_blendv_pd(_mm_set1_pd(1.0), _mm_set1_pd(-1.0), negative),
+            }
+        }
+    }
+
+    #[inline]
+    fn mul_add(&self, mul: Dvec2, add: Dvec2) -> Dvec2 {
+        unsafe {
+            Dvec2 {
+                inner: _mm_fmadd_pd(self.inner, mul.inner, add.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn mul_sub(&self, mul: Dvec2, sub: Dvec2) -> Dvec2 {
+        unsafe {
+            Dvec2 {
+                inner: _mm_fmsub_pd(self.inner, mul.inner, sub.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn neg_mul_add(&self, mul: Dvec2, add: Dvec2) -> Dvec2 {
+        unsafe {
+            Dvec2 {
+                inner: _mm_fnmadd_pd(self.inner, mul.inner, add.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn neg_mul_sub(&self, mul: Dvec2, sub: Dvec2) -> Dvec2 {
+        unsafe {
+            Dvec2 {
+                inner: _mm_fnmsub_pd(self.inner, mul.inner, sub.inner),
+            }
+        }
+    }
+
     #[inline]
     fn min_reduce(&self) -> f64 {
         unsafe {
@@ -153,15 +218,34 @@ impl Vec2<f64> for Dvec2 {
         }
     }
 
+    #[inline]
+    fn minmax_reduce(&self) -> (f64, f64) {
+        unsafe {
+            let perm = _mm_permute_pd::<1>(self.inner);
+            (
+                _mm_cvtsd_f64(_mm_min_pd(self.inner, perm)),
+                _mm_cvtsd_f64(_mm_max_pd(self.inner, perm)),
+            )
+        }
+    }
+
     #[inline]
     fn eq_reduce(&self, rhs: Dvec2) -> bool {
         unsafe {
             let mask = _mm_cmpeq_pd(self.inner, rhs.inner);
-            let reduce = _mm_movemask_epi8(std::mem::transmute(mask));
+            let reduce = _mm_movemask_epi8(_mm_castpd_si128(mask));
             reduce == 0xffff
         }
     }
 
+    #[inline]
+    fn ulps_eq(&self, rhs: Dvec2, max_ulps: u32) -> bool {
+        self.as_slice()
+            .iter()
+            .zip(rhs.as_slice())
+            .all(|(&a, &b)| ulps_eq_f64(a, b, max_ulps))
+    }
+
     #[inline]
     fn dot(&self, rhs: Dvec2) -> f64 {
         unsafe {
@@ -170,11 +254,152 @@ impl Vec2<f64> for Dvec2 {
             _mm_cvtsd_f64(reduce64)
         }
     }
+
+    #[inline]
+    fn nan_to_num(&self, nan: f64, pos_inf: f64, neg_inf: f64) -> Dvec2 {
+        unsafe {
+            // NaN is the only value that is unordered with itself
+            let nan_mask = _mm_cmpunord_pd(self.inner, self.inner);
+            let pos_mask = _mm_cmpeq_pd(self.inner, _mm_set1_pd(f64::INFINITY));
+            let neg_mask = _mm_cmpeq_pd(self.inner, _mm_set1_pd(f64::NEG_INFINITY));
+            let mut result = _mm_blendv_pd(self.inner, _mm_set1_pd(nan), nan_mask);
+            result = _mm_blendv_pd(result, _mm_set1_pd(pos_inf), pos_mask);
+            result = _mm_blendv_pd(result, _mm_set1_pd(neg_inf), neg_mask);
+            Dvec2 { inner: result }
+        }
+    }
+}
+
+impl Vec2<f64> for Dvec2 {
+    #[inline]
+    fn new(x: f64, y: f64) -> Dvec2 {
+        unsafe {
+            // The order is reversed!
+            Dvec2 {
+                inner: _mm_set_pd(y, x),
+            }
+        }
+    }
+
+    #[inline]
+    fn as_array(&self) -> &[f64; 2] {
+        unsafe { &*(self as *const Dvec2 as *const [f64; 2]) }
+    }
+
+    #[inline]
+    fn as_mut_array(&mut self) -> &mut [f64; 2] {
+        unsafe { &mut *(self as *mut Dvec2 as *mut [f64; 2]) }
+    }
+
+    #[inline]
+    fn flip_axes(&self, mask: [bool; 2]) -> Dvec2 {
+        let sign = |flip: bool| if flip { -0.0 } else { 0.0 };
+        unsafe {
+            let signs = _mm_set_pd(sign(mask[1]), sign(mask[0]));
+            Dvec2 {
+                inner: _mm_xor_pd(self.inner, signs),
+            }
+        }
+    }
+}
+
+impl Dvec2 {
+    /// Same as [`Dvec4::to_bits`](crate::Dvec4::to_bits), see [`f64::to_bits`].
+    ///
+    /// ```
+    /// use mafs::{Vec2, Dvec2};
+    ///
+    /// let v = Dvec2::new(-0.0, f64::NAN);
+    /// assert_eq!(Dvec2::from_bits(v.to_bits()).to_bits(), v.to_bits());
+    /// assert_eq!(v.to_bits()[0], 1 << 63);
+    /// ```
+    #[inline]
+    pub fn to_bits(&self) -> [u64; 2] {
+        self.as_array().map(f64::to_bits)
+    }
+
+    /// Create a vector from the raw IEEE 754 representation of each component, see [`f64::from_bits`].
+    #[inline]
+    pub fn from_bits(bits: [u64; 2]) -> Dvec2 {
+        let [x, y] = bits.map(f64::from_bits);
+        Dvec2::new(x, y)
+    }
 }
 
 implement_scalarops!(Dvec2, f64);
 implement_vecops!(Dvec2, f64);
 
+/// Signed area of a simple polygon with the shoelace formula: positive if the vertices are in counterclockwise
+/// order, negative if they are clockwise. The polygon is implicitly closed. Fewer than three points give zero.
+///
+/// ```
+/// use mafs::{Vec2, Dvec2, polygon_area};
+///
+/// let square = [
+///     Dvec2::new(0.0, 0.0),
+///     Dvec2::new(1.0, 0.0),
+///     Dvec2::new(1.0, 1.0),
+///     Dvec2::new(0.0, 1.0),
+/// ];
+/// assert_eq!(polygon_area(&square), 1.0);
+/// ```
+pub fn polygon_area(points: &[Dvec2]) -> f64 {
+    let Some(&last) = points.last() else {
+        return 0.0;
+    };
+    let mut previous = last;
+    let mut sum = 0.0;
+    for &p in points {
+        sum += previous.wedge(p);
+        previous = p;
+    }
+    sum * 0.5
+}
+
+/// Same as [`closest_point_on_segment`](crate::closest_point_on_segment), for 2D points.
+///
+/// ```
+/// use mafs::{Vec2, Dvec2, closest_point_on_segment2};
+///
+/// let (a, b) = (Dvec2::new(0.0, 0.0), Dvec2::new(4.0, 0.0));
+/// assert_eq!(closest_point_on_segment2(Dvec2::new(1.0, 3.0), a, b), Dvec2::new(1.0, 0.0));
+/// assert_eq!(closest_point_on_segment2(Dvec2::new(-2.0, 1.0), a, b), a);
+/// ```
+pub fn closest_point_on_segment2(p: Dvec2, a: Dvec2, b: Dvec2) -> Dvec2 {
+    let ab = b - a;
+    let length_squared = ab.norm_squared();
+    if length_squared == 0.0 {
+        return a;
+    }
+    let t = ((p - a).dot(ab) / length_squared).clamp(0.0, 1.0);
+    a.lerp(b, t)
+}
+
+/// Intersection of the line through `p0` with direction `d0` and the line through `p1` with direction `d1`.
+///
+/// Returns `None` if the lines are parallel, including when they are the same line, i.e. when the sine of
+/// the angle between the directions is within rounding error of zero. The directions need not be unit length.
+///
+/// ```
+/// use mafs::{Vec2, Dvec2, line_intersection};
+///
+/// let x_axis = (Dvec2::new(3.0, 0.0), Dvec2::new(1.0, 0.0));
+/// let y_axis = (Dvec2::new(0.0, -2.0), Dvec2::new(0.0, 5.0));
+/// assert_eq!(line_intersection(x_axis.0, x_axis.1, y_axis.0, y_axis.1), Some(Dvec2::new(0.0, 0.0)));
+///
+/// let parallel = (Dvec2::new(0.0, 1.0), Dvec2::new(-2.0, 0.0));
+/// assert_eq!(line_intersection(x_axis.0, x_axis.1, parallel.0, parallel.1), None);
+/// assert_eq!(line_intersection(x_axis.0, x_axis.1, x_axis.0, x_axis.1), None);
+/// ```
+pub fn line_intersection(p0: Dvec2, d0: Dvec2, p1: Dvec2, d1: Dvec2) -> Option<Dvec2> {
+    let det = d0.wedge(d1);
+    if det.abs() <= f64::EPSILON * d0.norm() * d1.norm() {
+        return None;
+    }
+    let t = (p1 - p0).wedge(d1) / det;
+    Some(p0 + d0 * t)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,4 +421,21 @@ mod tests {
         let f = Dvec2::new(f64::NAN, f64::NAN);
         assert_eq!(f == f, false);
     }
+
+    #[test]
+    fn minmax_reduce_works() {
+        assert_eq!(Dvec2::new(3.0, -1.0).minmax_reduce(), (-1.0, 3.0));
+
+        // The result must match the separate reductions bit for bit, including for NaN and signed zeros
+        for v in [
+            Dvec2::new(3.0, -1.0),
+            Dvec2::new(-0.0, 0.0),
+            Dvec2::new(f64::NAN, 1.0),
+            Dvec2::new(1.0, f64::NAN),
+        ] {
+            let (min, max) = v.minmax_reduce();
+            assert_eq!(min.to_bits(), v.min_reduce().to_bits());
+            assert_eq!(max.to_bits(), v.max_reduce().to_bits());
+        }
+    }
 }
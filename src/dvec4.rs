@@ -1,4 +1,4 @@
-use crate::Vec4;
+use crate::{Dmat4, Dvec2, LengthError, Mat4, Vec2, Vec4, Vector};
 use std::arch::x86_64::*;
 
 /// 4D vector with double precision
@@ -8,7 +8,7 @@ use std::arch::x86_64::*;
 /// ## Examples
 ///
 /// ```
-/// use mafs::{Vec4, Dvec4};
+/// use mafs::{Vec4, Vector, Dvec4};
 ///
 /// // Construction
 /// let a = Dvec4::new(2.0, 3.0, 5.0, 6.0);
@@ -59,24 +59,24 @@ impl std::fmt::Debug for Dvec4 {
     }
 }
 
-impl Vec4<f64> for Dvec4 {
+impl Vector<f64> for Dvec4 {
     #[inline]
-    fn new(x: f64, y: f64, z: f64, w: f64) -> Dvec4 {
+    fn splat(value: f64) -> Dvec4 {
         unsafe {
             Dvec4 {
-                inner: _mm256_set_pd(w, z, y, x),
+                inner: _mm256_set1_pd(value),
             }
         }
     }
 
     #[inline]
-    fn as_array(&self) -> &[f64; 4] {
-        unsafe { &*(self as *const Dvec4 as *const [f64; 4]) }
+    fn as_slice(&self) -> &[f64] {
+        self.as_array()
     }
 
     #[inline]
-    fn as_mut_array(&mut self) -> &mut [f64; 4] {
-        unsafe { &mut *(self as *mut Dvec4 as *mut [f64; 4]) }
+    fn as_mut_slice(&mut self) -> &mut [f64] {
+        self.as_mut_array()
     }
 
     #[inline]
@@ -142,6 +142,72 @@ impl Vec4<f64> for Dvec4 {
         }
     }
 
+    #[inline]
+    fn round(&self) -> Dvec4 {
+        unsafe {
+            Dvec4 {
+                inner: _mm256_round_pd::<{ _MM_FROUND_TO_NEAREST_INT | _MM_FROUND_NO_EXC }>(
+                    self.inner,
+                ),
+            }
+        }
+    }
+
+    #[inline]
+    fn abs(&self) -> Dvec4 {
+        unsafe {
+            Dvec4 {
+                inner: _mm256_andnot_pd(_mm256_set1_pd(-0.0), self.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn signum_nonzero(&self) -> Dvec4 {
+        unsafe {
+            let negative = _mm256_cmp_pd::<_CMP_LT_OQ>(self.inner, _mm256_setzero_pd());
+            Dvec4 {
+                inner: _mm256_blendv_pd(_mm256_set1_pd(1.0), _mm256_set1_pd(-1.0), negative),
+            }
+        }
+    }
+
+    #[inline]
+    fn mul_add(&self, mul: Dvec4, add: Dvec4) -> Dvec4 {
+        unsafe {
+            Dvec4 {
+                inner: _mm256_fmadd_pd(self.inner, mul.inner, add.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn mul_sub(&self, mul: Dvec4, sub: Dvec4) -> Dvec4 {
+        unsafe {
+            Dvec4 {
+                inner: _mm256_fmsub_pd(self.inner, mul.inner, sub.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn neg_mul_add(&self, mul: Dvec4, add: Dvec4) -> Dvec4 {
+        unsafe {
+            Dvec4 {
+                inner: _mm256_fnmadd_pd(self.inner, mul.inner, add.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn neg_mul_sub(&self, mul: Dvec4, sub: Dvec4) -> Dvec4 {
+        unsafe {
+            Dvec4 {
+                inner: _mm256_fnmsub_pd(self.inner, mul.inner, sub.inner),
+            }
+        }
+    }
+
     #[inline]
     fn min_reduce(&self) -> f64 {
         unsafe {
@@ -166,15 +232,40 @@ impl Vec4<f64> for Dvec4 {
         }
     }
 
+    #[inline]
+    fn minmax_reduce(&self) -> (f64, f64) {
+        unsafe {
+            let low = _mm256_castpd256_pd128(self.inner);
+            let high = _mm256_extractf128_pd::<1>(self.inner);
+            // Minimums in the low half, maximums in the high half
+            let both = _mm256_set_m128d(_mm_max_pd(low, high), _mm_min_pd(low, high));
+            let swapped = _mm256_permute_pd::<0b_01_01>(both);
+            let min = _mm256_min_pd(both, swapped);
+            let max = _mm256_max_pd(both, swapped);
+            (
+                _mm256_cvtsd_f64(min),
+                _mm_cvtsd_f64(_mm256_extractf128_pd::<1>(max)),
+            )
+        }
+    }
+
     #[inline]
     fn eq_reduce(&self, rhs: Dvec4) -> bool {
         unsafe {
             let mask = _mm256_cmp_pd::<_CMP_EQ_OQ>(self.inner, rhs.inner);
-            let reduce = _mm256_movemask_epi8(std::mem::transmute(mask));
+            let reduce = _mm256_movemask_epi8(_mm256_castpd_si256(mask));
             reduce as u32 == 0xffffffff
         }
     }
 
+    #[inline]
+    fn ulps_eq(&self, rhs: Dvec4, max_ulps: u32) -> bool {
+        self.as_slice()
+            .iter()
+            .zip(rhs.as_slice())
+            .all(|(&a, &b)| ulps_eq_f64(a, b, max_ulps))
+    }
+
     #[inline]
     fn dot(&self, rhs: Dvec4) -> f64 {
         unsafe {
@@ -188,6 +279,42 @@ impl Vec4<f64> for Dvec4 {
         }
     }
 
+    #[inline]
+    fn nan_to_num(&self, nan: f64, pos_inf: f64, neg_inf: f64) -> Dvec4 {
+        unsafe {
+            // NaN is the only value that is unordered with itself
+            let nan_mask = _mm256_cmp_pd::<_CMP_UNORD_Q>(self.inner, self.inner);
+            let pos_mask = _mm256_cmp_pd::<_CMP_EQ_OQ>(self.inner, _mm256_set1_pd(f64::INFINITY));
+            let neg_mask =
+                _mm256_cmp_pd::<_CMP_EQ_OQ>(self.inner, _mm256_set1_pd(f64::NEG_INFINITY));
+            let mut result = _mm256_blendv_pd(self.inner, _mm256_set1_pd(nan), nan_mask);
+            result = _mm256_blendv_pd(result, _mm256_set1_pd(pos_inf), pos_mask);
+            result = _mm256_blendv_pd(result, _mm256_set1_pd(neg_inf), neg_mask);
+            Dvec4 { inner: result }
+        }
+    }
+}
+
+impl Vec4<f64> for Dvec4 {
+    #[inline]
+    fn new(x: f64, y: f64, z: f64, w: f64) -> Dvec4 {
+        unsafe {
+            Dvec4 {
+                inner: _mm256_set_pd(w, z, y, x),
+            }
+        }
+    }
+
+    #[inline]
+    fn as_array(&self) -> &[f64; 4] {
+        unsafe { &*(self as *const Dvec4 as *const [f64; 4]) }
+    }
+
+    #[inline]
+    fn as_mut_array(&mut self) -> &mut [f64; 4] {
+        unsafe { &mut *(self as *mut Dvec4 as *mut [f64; 4]) }
+    }
+
     #[inline]
     fn cross(&self, rhs: Dvec4) -> Dvec4 {
         unsafe {
@@ -204,11 +331,596 @@ impl Vec4<f64> for Dvec4 {
             Dvec4 { inner: result }
         }
     }
+
+    #[inline]
+    fn flip_axes(&self, mask: [bool; 4]) -> Dvec4 {
+        let sign = |flip: bool| if flip { -0.0 } else { 0.0 };
+        unsafe {
+            let signs = _mm256_set_pd(sign(mask[3]), sign(mask[2]), sign(mask[1]), sign(mask[0]));
+            Dvec4 {
+                inner: _mm256_xor_pd(self.inner, signs),
+            }
+        }
+    }
+
+    #[inline]
+    fn min_reduce3(&self) -> f64 {
+        unsafe {
+            let inf = _mm256_set1_pd(f64::INFINITY);
+            Dvec4 {
+                inner: _mm256_blend_pd::<0b_1000>(self.inner, inf),
+            }
+            .min_reduce()
+        }
+    }
+
+    #[inline]
+    fn max_reduce3(&self) -> f64 {
+        unsafe {
+            let neg_inf = _mm256_set1_pd(f64::NEG_INFINITY);
+            Dvec4 {
+                inner: _mm256_blend_pd::<0b_1000>(self.inner, neg_inf),
+            }
+            .max_reduce()
+        }
+    }
+
+    #[inline]
+    fn dot_fma(&self, rhs: Dvec4) -> f64 {
+        unsafe {
+            let prod = _mm_mul_pd(
+                _mm256_castpd256_pd128(self.inner),
+                _mm256_castpd256_pd128(rhs.inner),
+            );
+            let reduce128 = _mm_fmadd_pd(
+                _mm256_extractf128_pd::<1>(self.inner),
+                _mm256_extractf128_pd::<1>(rhs.inner),
+                prod,
+            );
+            let reduce64 = _mm_add_sd(reduce128, _mm_permute_pd::<1>(reduce128));
+            _mm_cvtsd_f64(reduce64)
+        }
+    }
+}
+
+impl Dvec4 {
+    /// Raw IEEE 754 representation of each component, see [`f64::to_bits`].
+    /// Unlike the comparison with `==`, this distinguishes `0.0` from `-0.0` and preserves NaN payloads.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// let v = Dvec4::new(1.0, -0.0, f64::NAN, f64::INFINITY);
+    /// assert_eq!(Dvec4::from_bits(v.to_bits()).to_bits(), v.to_bits());
+    /// assert_eq!(v.to_bits()[1], 1 << 63);
+    /// ```
+    #[inline]
+    pub fn to_bits(&self) -> [u64; 4] {
+        self.as_array().map(f64::to_bits)
+    }
+
+    /// Create a vector from the raw IEEE 754 representation of each component, see [`f64::from_bits`].
+    #[inline]
+    pub fn from_bits(bits: [u64; 4]) -> Dvec4 {
+        let [x, y, z, w] = bits.map(f64::from_bits);
+        Dvec4::new(x, y, z, w)
+    }
+
+    /// Assemble a vector from its two halves: `xy` in lanes 0 and 1, `zw` in lanes 2 and 3.
+    ///
+    /// ```
+    /// use mafs::{Vec2, Dvec2, Vec4, Dvec4};
+    ///
+    /// let v = Dvec4::from_parts(Dvec2::new(1.0, 2.0), Dvec2::new(3.0, 4.0));
+    /// assert_eq!(v, Dvec4::new(1.0, 2.0, 3.0, 4.0));
+    /// assert_eq!(v.split(), (Dvec2::new(1.0, 2.0), Dvec2::new(3.0, 4.0)));
+    /// ```
+    #[inline]
+    pub fn from_parts(xy: Dvec2, zw: Dvec2) -> Dvec4 {
+        unsafe {
+            Dvec4 {
+                inner: _mm256_set_m128d(zw.inner, xy.inner),
+            }
+        }
+    }
+
+    /// Split this vector into its two halves, the inverse of [`Dvec4::from_parts`].
+    #[inline]
+    pub fn split(&self) -> (Dvec2, Dvec2) {
+        unsafe {
+            (
+                Dvec2 {
+                    inner: _mm256_castpd256_pd128(self.inner),
+                },
+                Dvec2 {
+                    inner: _mm256_extractf128_pd::<1>(self.inner),
+                },
+            )
+        }
+    }
+
+    /// Create a vector from a slice of exactly 4 elements.
+    /// Unlike the conversion from an array, the length is only known at runtime, so it is checked.
+    #[inline]
+    pub fn try_from_vec(v: &[f64]) -> Result<Dvec4, LengthError> {
+        match *v {
+            [x, y, z, w] => Ok(Dvec4::new(x, y, z, w)),
+            _ => Err(LengthError {
+                expected: 4,
+                actual: v.len(),
+            }),
+        }
+    }
+
+    /// Copy the components into a `Vec`.
+    #[inline]
+    pub fn into_vec(&self) -> Vec<f64> {
+        self.as_slice().to_vec()
+    }
+
+    /// Octahedral encoding of a unit 3D direction into a point of `[-1, 1]²`, for compact normal storage.
+    /// The direction is projected on the octahedron `|x| + |y| + |z| = 1`, whose lower half (`z < 0`)
+    /// is then unfolded over the corners of the square. The fourth component is ignored.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let directions = [
+    ///     Dvec4::direction(0.0, 0.0, 1.0),
+    ///     Dvec4::direction(0.0, 0.0, -1.0),
+    ///     Dvec4::direction(1.0, 0.0, 0.0),
+    ///     Dvec4::direction(0.0, -0.6, 0.8),
+    ///     Dvec4::direction(-0.48, 0.64, -0.6),
+    /// ];
+    /// for d in directions {
+    ///     let decoded = Dvec4::octahedral_decode(d.octahedral_encode());
+    ///     assert!(decoded.dot3(d) > 1.0 - 1e-12);
+    /// }
+    /// ```
+    #[inline]
+    pub fn octahedral_encode(&self) -> Dvec2 {
+        let [x, y, z, _] = *self.as_array();
+        let l1 = x.abs() + y.abs() + z.abs();
+        let (x, y) = (x / l1, y / l1);
+        if z < 0.0 {
+            let sign = |v: f64| if v >= 0.0 { 1.0 } else { -1.0 };
+            Dvec2::new((1.0 - y.abs()) * sign(x), (1.0 - x.abs()) * sign(y))
+        } else {
+            Dvec2::new(x, y)
+        }
+    }
+
+    /// Decode a direction encoded with [`Dvec4::octahedral_encode`]. The result is unit length,
+    /// with a fourth component of zero.
+    #[inline]
+    pub fn octahedral_decode(oct: Dvec2) -> Dvec4 {
+        let [x, y] = *oct.as_array();
+        let z = 1.0 - x.abs() - y.abs();
+        let result = if z < 0.0 {
+            let sign = |v: f64| if v >= 0.0 { 1.0 } else { -1.0 };
+            Dvec4::direction((1.0 - y.abs()) * sign(x), (1.0 - x.abs()) * sign(y), z)
+        } else {
+            Dvec4::direction(x, y, z)
+        };
+        result.normalize()
+    }
+
+    /// Sums of the two halves of this vector: `(x + y, z + w)`.
+    /// Useful when lanes 0 and 1 hold one 2D problem and lanes 2 and 3 hold another.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::new(1.0, 2.0, 10.0, 20.0).pair_sums(), (3.0, 30.0));
+    /// ```
+    #[inline]
+    pub fn pair_sums(&self) -> (f64, f64) {
+        unsafe {
+            let sums = _mm256_hadd_pd(self.inner, self.inner);
+            (
+                _mm256_cvtsd_f64(sums),
+                _mm_cvtsd_f64(_mm256_extractf128_pd::<1>(sums)),
+            )
+        }
+    }
+
+    /// Dot products of the two halves of the vectors, with the same lane pairing as [`Dvec4::pair_sums`]:
+    /// `(x0 * x1 + y0 * y1, z0 * z1 + w0 * w1)`.
+    #[inline]
+    pub fn pair_dots(&self, rhs: Dvec4) -> (f64, f64) {
+        (*self * rhs).pair_sums()
+    }
+
+    /// Create a vector whose lane `i` is `data[indices[i]]`.
+    /// Panics if any index is out of bounds.
+    ///
+    /// This is a scalar implementation, the AVX2 gather instructions are not used yet.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// let v = Dvec4::gather(&[10.0, 20.0, 30.0, 40.0], [3, 2, 1, 0]);
+    /// assert_eq!(v, Dvec4::new(40.0, 30.0, 20.0, 10.0));
+    ///
+    /// let mut data = [0.0; 6];
+    /// v.scatter(&mut data, [5, 4, 1, 0]);
+    /// assert_eq!(data, [10.0, 20.0, 0.0, 0.0, 30.0, 40.0]);
+    /// ```
+    #[inline]
+    pub fn gather(data: &[f64], indices: [usize; 4]) -> Dvec4 {
+        let [x, y, z, w] = indices.map(|i| data[i]);
+        Dvec4::new(x, y, z, w)
+    }
+
+    /// Write lane `i` of this vector to `data[indices[i]]`.
+    /// If an index is repeated, the last lane wins. Panics if any index is out of bounds.
+    #[inline]
+    pub fn scatter(&self, data: &mut [f64], indices: [usize; 4]) {
+        for (&x, i) in self.as_array().iter().zip(indices) {
+            data[i] = x;
+        }
+    }
 }
 
 implement_scalarops!(Dvec4, f64);
 implement_vecops!(Dvec4, f64);
 
+implement_ulps_eq!(ulps_eq_f64, f64, i64, u64);
+
+/// Weighted sum of vectors, i.e. `w0 * v0 + w1 * v1 + ...`.
+/// Each step is accumulated with a fused multiply-add. The sum of an empty slice is the zero vector.
+#[inline]
+pub fn weighted_sum(pairs: &[(f64, Dvec4)]) -> Dvec4 {
+    unsafe {
+        let mut result = _mm256_setzero_pd();
+        for (w, v) in pairs {
+            result = _mm256_fmadd_pd(_mm256_set1_pd(*w), v.inner, result);
+        }
+        Dvec4 { inner: result }
+    }
+}
+
+/// Weighted sum of exactly four vectors, e.g. for four-bone skinning.
+/// Same as [`weighted_sum`] but unrolled.
+#[inline]
+pub fn weighted_sum4(w: [f64; 4], v: [Dvec4; 4]) -> Dvec4 {
+    unsafe {
+        let mut result = _mm256_mul_pd(_mm256_set1_pd(w[0]), v[0].inner);
+        result = _mm256_fmadd_pd(_mm256_set1_pd(w[1]), v[1].inner, result);
+        result = _mm256_fmadd_pd(_mm256_set1_pd(w[2]), v[2].inner, result);
+        result = _mm256_fmadd_pd(_mm256_set1_pd(w[3]), v[3].inner, result);
+        Dvec4 { inner: result }
+    }
+}
+
+/// Accumulator for sums of the form `Σ coeff * v`, where each step is a fused multiply-add,
+/// i.e. it is rounded only once.
+///
+/// ```
+/// use mafs::{Vec4, Dvec4, FmaAccumulator};
+///
+/// let mut acc = FmaAccumulator::new();
+/// acc.mul_add(2.0, Dvec4::new(1.0, 2.0, 3.0, 4.0));
+/// acc.mul_add(-1.0, Dvec4::new(1.0, 1.0, 1.0, 1.0));
+/// assert_eq!(acc.finish(), Dvec4::new(1.0, 3.0, 5.0, 7.0));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FmaAccumulator {
+    sum: Dvec4,
+}
+
+impl FmaAccumulator {
+    /// Create an accumulator starting from zero.
+    #[inline]
+    pub fn new() -> FmaAccumulator {
+        FmaAccumulator::default()
+    }
+
+    /// Add `coeff * v` to the sum.
+    #[inline]
+    pub fn mul_add(&mut self, coeff: f64, v: Dvec4) {
+        unsafe {
+            self.sum.inner = _mm256_fmadd_pd(_mm256_set1_pd(coeff), v.inner, self.sum.inner);
+        }
+    }
+
+    /// Get the sum.
+    #[inline]
+    pub fn finish(self) -> Dvec4 {
+        self.sum
+    }
+}
+
+/// Componentwise Kahan summation: the rounding error of each addition is kept in a compensation term
+/// and fed back into the next one.
+///
+/// This costs three more additions per step than a plain sum, but the error no longer grows with the number
+/// of terms, which matters when accumulating millions of small values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KahanSum4 {
+    sum: Dvec4,
+    compensation: Dvec4,
+}
+
+impl KahanSum4 {
+    /// Create an accumulator starting from zero.
+    #[inline]
+    pub fn new() -> KahanSum4 {
+        KahanSum4::default()
+    }
+
+    /// Add `x` to the sum.
+    #[inline]
+    pub fn add(&mut self, x: Dvec4) {
+        let y = x - self.compensation;
+        let t = self.sum + y;
+        self.compensation = (t - self.sum) - y;
+        self.sum = t;
+    }
+
+    /// Get the sum.
+    #[inline]
+    pub fn sum(self) -> Dvec4 {
+        self.sum
+    }
+}
+
+/// Online mean and variance of a stream of vectors, computed componentwise with Welford's algorithm.
+/// This is numerically stable even when the values have a large common offset, and does not store the values.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunningStats4 {
+    count: u64,
+    mean: Dvec4,
+    m2: Dvec4,
+}
+
+impl RunningStats4 {
+    /// Create empty statistics.
+    #[inline]
+    pub fn new() -> RunningStats4 {
+        RunningStats4::default()
+    }
+
+    /// Add a value.
+    #[inline]
+    pub fn push(&mut self, x: Dvec4) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Number of values pushed so far.
+    #[inline]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Mean of the values, zero if there are none.
+    #[inline]
+    pub fn mean(&self) -> Dvec4 {
+        self.mean
+    }
+
+    /// Population variance of the values, i.e. the mean of the squared deviations, dividing by `n`.
+    /// For the unbiased sample variance, multiply by `n / (n - 1)`. NaN if there are no values.
+    #[inline]
+    pub fn variance(&self) -> Dvec4 {
+        self.m2 / self.count as f64
+    }
+
+    /// Population standard deviation of the values, i.e. the square root of [`RunningStats4::variance`].
+    #[inline]
+    pub fn std_dev(&self) -> Dvec4 {
+        self.variance().map(f64::sqrt)
+    }
+}
+
+/// Blend four vectors, using the components of `weights` as the blend factors:
+/// `v[0] * weights[0] + v[1] * weights[1] + v[2] * weights[2] + v[3] * weights[3]`.
+/// Same as [`weighted_sum4`], the weights are not normalized.
+///
+/// ```
+/// use mafs::{Vec4, Vector, Dvec4, blend4};
+///
+/// let v = [
+///     Dvec4::new(1.0, 0.0, 0.0, 1.0),
+///     Dvec4::new(0.0, 1.0, 0.0, 1.0),
+///     Dvec4::new(0.0, 0.0, 1.0, 1.0),
+///     Dvec4::new(1.0, 1.0, 1.0, 1.0),
+/// ];
+/// assert_eq!(blend4(v, Dvec4::new(1.0, 0.0, 0.0, 0.0)), v[0]);
+/// assert_eq!(blend4(v, Dvec4::splat(0.25)), Dvec4::new(0.5, 0.5, 0.5, 1.0));
+/// ```
+#[inline]
+pub fn blend4(v: [Dvec4; 4], weights: Dvec4) -> Dvec4 {
+    Dmat4::from_columns(v[0], v[1], v[2], v[3]).mul_vector(weights)
+}
+
+/// Index of the point that goes the furthest along `dir`, i.e. that maximizes `point.dot(dir)`.
+/// Returns `None` if the slice is empty. In case of a tie, the lowest index is returned.
+#[inline]
+pub fn argmax_dot(points: &[Dvec4], dir: Dvec4) -> Option<usize> {
+    let mut best = None;
+    let mut best_dot = f64::NEG_INFINITY;
+    for (i, p) in points.iter().enumerate() {
+        let d = p.dot(dir);
+        if best.is_none() || d > best_dot {
+            best = Some(i);
+            best_dot = d;
+        }
+    }
+    best
+}
+
+/// Signed distance from a point to a plane.
+///
+/// The plane is given as `(nx, ny, nz, d)`, i.e. the points `p` of the plane verify `n.p + d = 0`,
+/// with `n` a unit normal. The point must have its fourth component equal to 1.
+#[inline]
+pub fn plane_distance(point: Dvec4, plane: Dvec4) -> f64 {
+    point.dot(plane)
+}
+
+/// Mirror a point across a plane, e.g. to render a mirror or a portal.
+/// Same conventions as [`plane_distance`]: the normal of the plane is unit length and the point has its fourth
+/// component equal to 1.
+///
+/// ```
+/// use mafs::{Vec4, Dvec4, reflect_point};
+///
+/// let point = Dvec4::new(0.0, 2.0, 0.0, 1.0);
+/// let plane = Dvec4::new(0.0, 1.0, 0.0, 0.0);
+/// assert_eq!(reflect_point(point, plane), Dvec4::new(0.0, -2.0, 0.0, 1.0));
+///
+/// let plane = Dvec4::new(0.0, 1.0, 0.0, -1.0); // The plane y = 1
+/// assert_eq!(reflect_point(point, plane), Dvec4::new(0.0, 0.0, 0.0, 1.0));
+/// ```
+#[inline]
+pub fn reflect_point(point: Dvec4, plane: Dvec4) -> Dvec4 {
+    let normal = Dvec4::direction(plane[0], plane[1], plane[2]);
+    point - normal * (2.0 * plane_distance(point, plane))
+}
+
+/// Clip the segment `[a, b]` against a plane, keeping the part where [`plane_distance`] is positive or zero.
+/// Same conventions as [`plane_distance`] for the plane and the points.
+///
+/// Returns `None` if the whole segment is on the negative side. Otherwise, the endpoints on the negative side
+/// are moved to the point where the segment crosses the plane.
+#[inline]
+pub fn clip_segment_to_plane(a: Dvec4, b: Dvec4, plane: Dvec4) -> Option<(Dvec4, Dvec4)> {
+    let da = plane_distance(a, plane);
+    let db = plane_distance(b, plane);
+    match (da >= 0.0, db >= 0.0) {
+        (true, true) => Some((a, b)),
+        (false, false) => None,
+        (true, false) => Some((a, a.lerp(b, da / (da - db)))),
+        (false, true) => Some((a.lerp(b, da / (da - db)), b)),
+    }
+}
+
+/// Closest point to `p` on the segment `[a, b]`.
+/// The points are treated as 3D points: the fourth components do not affect the result, which lies on the
+/// segment and so is still a point if `a` and `b` are. If `a` and `b` are equal, `a` is returned.
+///
+/// ```
+/// use mafs::{Vec4, Dvec4, closest_point_on_segment};
+///
+/// let (a, b) = (Dvec4::point(0.0, 0.0, 0.0), Dvec4::point(4.0, 0.0, 0.0));
+/// assert_eq!(closest_point_on_segment(Dvec4::point(2.0, 3.0, -1.0), a, b), Dvec4::point(2.0, 0.0, 0.0));
+/// assert_eq!(closest_point_on_segment(Dvec4::point(7.0, 1.0, 0.0), a, b), b);
+/// ```
+#[inline]
+pub fn closest_point_on_segment(p: Dvec4, a: Dvec4, b: Dvec4) -> Dvec4 {
+    let ab = b - a;
+    let length_squared = ab.dot3(ab);
+    if length_squared == 0.0 {
+        return a;
+    }
+    let t = ((p - a).dot3(ab) / length_squared).clamp(0.0, 1.0);
+    a.lerp(b, t)
+}
+
+/// Dot product of each vector of `vecs` with `against`, written to the corresponding slot of `out`.
+///
+/// The vectors are processed four at a time: they are transposed so that the four dot products are computed
+/// together without any horizontal reduction. Panics if `vecs` and `out` do not have the same length.
+#[inline]
+pub fn dot_many(vecs: &[Dvec4], against: Dvec4, out: &mut [f64]) {
+    assert_eq!(vecs.len(), out.len());
+    let mut vecs_chunks = vecs.chunks_exact(4);
+    let mut out_chunks = out.chunks_exact_mut(4);
+    for (v, o) in (&mut vecs_chunks).zip(&mut out_chunks) {
+        let rows = Dmat4::from_columns(v[0], v[1], v[2], v[3]).transpose();
+        o.copy_from_slice(rows.mul_vector(against).as_array());
+    }
+    for (v, o) in vecs_chunks
+        .remainder()
+        .iter()
+        .zip(out_chunks.into_remainder())
+    {
+        *o = v.dot(against);
+    }
+}
+
+/// Sum of the dot products of many pairs of vectors.
+///
+/// The products are accumulated componentwise with fused multiply-adds, and the four lanes are only added together
+/// at the end. There is no Kahan compensation. The sum of an empty slice is zero.
+#[inline]
+pub fn dot_accumulate(pairs: &[(Dvec4, Dvec4)]) -> f64 {
+    unsafe {
+        let mut result = _mm256_setzero_pd();
+        for (a, b) in pairs {
+            result = _mm256_fmadd_pd(a.inner, b.inner, result);
+        }
+        Dvec4 { inner: result }.dot(Dvec4::splat(1.0))
+    }
+}
+
+/// Barycentric coordinates `(u, v, w)` of `p` with respect to the triangle `abc`, such that `u + v + w == 1`
+/// and `u * a + v * b + w * c` is the projection of `p` on the plane of the triangle.
+/// The points are treated as 3D points, the fourth component is ignored.
+/// The point is inside the triangle if the three coordinates are non-negative.
+///
+/// The coordinates are obtained with Cramer's rule on the dot products of the edges. If the triangle is degenerate
+/// (its vertices are collinear), the system has no unique solution and the result contains NaN or infinities.
+///
+/// ```
+/// use mafs::{Vec4, Dvec4, barycentric_coords};
+///
+/// let a = Dvec4::point(0.0, 0.0, 1.0);
+/// let b = Dvec4::point(3.0, 0.0, 1.0);
+/// let c = Dvec4::point(0.0, 3.0, 4.0);
+/// assert_eq!(barycentric_coords(a, a, b, c), (1.0, 0.0, 0.0));
+///
+/// let centroid = Dvec4::point(1.0, 1.0, 2.0);
+/// let (u, v, w) = barycentric_coords(centroid, a, b, c);
+/// assert!((u - 1.0 / 3.0).abs() < 1e-12 && (v - 1.0 / 3.0).abs() < 1e-12 && (w - 1.0 / 3.0).abs() < 1e-12);
+/// ```
+#[inline]
+pub fn barycentric_coords(p: Dvec4, a: Dvec4, b: Dvec4, c: Dvec4) -> (f64, f64, f64) {
+    let (ab, ac, ap) = (b - a, c - a, p - a);
+    let (d00, d01, d11) = (ab.dot3(ab), ab.dot3(ac), ac.dot3(ac));
+    let (d20, d21) = (ap.dot3(ab), ap.dot3(ac));
+    let denominator = d00 * d11 - d01 * d01;
+    let v = (d11 * d20 - d01 * d21) / denominator;
+    let w = (d00 * d21 - d01 * d20) / denominator;
+    (1.0 - v - w, v, w)
+}
+
+/// Whichever of `a` and `b` has the smallest component along `axis`. Ties return `a`.
+/// Panics if `axis` is out of bounds.
+///
+/// ```
+/// use mafs::{Vec4, Dvec4, min_by_axis, max_by_axis};
+///
+/// let a = Dvec4::new(0.0, 5.0, 0.0, 0.0);
+/// let b = Dvec4::new(0.0, 3.0, 0.0, 0.0);
+/// assert_eq!(min_by_axis(a, b, 1), b);
+/// assert_eq!(max_by_axis(a, b, 1), a);
+/// assert_eq!(min_by_axis(a, b, 0), a);
+/// ```
+#[inline]
+pub fn min_by_axis(a: Dvec4, b: Dvec4, axis: usize) -> Dvec4 {
+    if b[axis] < a[axis] {
+        b
+    } else {
+        a
+    }
+}
+
+/// Whichever of `a` and `b` has the largest component along `axis`. Ties return `a`.
+/// Panics if `axis` is out of bounds.
+#[inline]
+pub fn max_by_axis(a: Dvec4, b: Dvec4, axis: usize) -> Dvec4 {
+    if b[axis] > a[axis] {
+        b
+    } else {
+        a
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,4 +942,288 @@ mod tests {
         let f = Dvec4::new(f64::NAN, f64::NAN, f64::NAN, f64::NAN);
         assert_eq!(f == f, false);
     }
+
+    #[test]
+    fn weighted_sum_works() {
+        assert_eq!(weighted_sum(&[]), Dvec4::splat(0.0));
+
+        let w = [2.0, -1.0, 0.5, 0.0];
+        let v = [
+            Dvec4::new(1.0, 2.0, 3.0, 4.0),
+            Dvec4::new(0.0, 1.0, -1.0, 2.0),
+            Dvec4::new(4.0, -2.0, 6.0, 0.0),
+            Dvec4::new(9.0, 9.0, 9.0, 9.0),
+        ];
+        let expected = Dvec4::new(4.0, 2.0, 10.0, 6.0);
+        let pairs = [(w[0], v[0]), (w[1], v[1]), (w[2], v[2]), (w[3], v[3])];
+        assert_eq!(weighted_sum(&pairs), expected);
+        assert_eq!(weighted_sum(&pairs[..1]), Dvec4::new(2.0, 4.0, 6.0, 8.0));
+        assert_eq!(weighted_sum4(w, v), expected);
+    }
+
+    #[test]
+    fn dot_many_works() {
+        // One chunk of four and a remainder of one
+        let vecs = [
+            Dvec4::new(1.0, 2.0, 3.0, 4.0),
+            Dvec4::new(0.0, -1.0, 2.0, 0.5),
+            Dvec4::new(2.0, 2.0, 2.0, 2.0),
+            Dvec4::new(-3.0, 0.0, 1.0, 1.0),
+            Dvec4::new(0.25, 4.0, -2.0, 0.0),
+        ];
+        let against = Dvec4::new(1.0, -1.0, 2.0, 0.5);
+        let mut out = [0.0; 5];
+        dot_many(&vecs, against, &mut out);
+        assert_eq!(out, [7.0, 5.25, 5.0, -0.5, -7.75]);
+
+        dot_many(&vecs[4..], against, &mut out[..1]);
+        assert_eq!(out[0], -7.75);
+        dot_many(&[], against, &mut []);
+    }
+
+    #[test]
+    fn dot_fma_works() {
+        // x * x - 1 where the rounding of x * x loses the lowest bits
+        let x = 1.0 + 2.0f64.powi(-30);
+        let exact = 2.0f64.powi(-29) + 2.0f64.powi(-60);
+        let a = Dvec4::new(-1.0, 0.0, x, 0.0);
+        let b = Dvec4::new(1.0, 0.0, x, 0.0);
+        assert_eq!(a.dot(b), 2.0f64.powi(-29));
+        assert_eq!(a.dot_fma(b), exact);
+        let minus_one = (
+            Dvec4::new(0.0, 0.0, -1.0, 0.0),
+            Dvec4::new(0.0, 0.0, 1.0, 0.0),
+        );
+        let x_squared = (Dvec4::new(0.0, 0.0, x, 0.0), Dvec4::new(0.0, 0.0, x, 0.0));
+        assert_eq!(dot_accumulate(&[minus_one, x_squared]), exact);
+
+        assert_eq!(dot_accumulate(&[]), 0.0);
+        let pairs = [
+            (
+                Dvec4::new(1.0, 2.0, 3.0, 4.0),
+                Dvec4::new(5.0, 6.0, 7.0, 8.0),
+            ),
+            (
+                Dvec4::new(-1.0, 0.5, 2.0, 1.0),
+                Dvec4::new(2.0, 4.0, -1.0, 3.0),
+            ),
+        ];
+        assert_eq!(dot_accumulate(&pairs), 70.0 + 1.0);
+    }
+
+    #[test]
+    fn argmax_dot_works() {
+        assert_eq!(argmax_dot(&[], Dvec4::splat(1.0)), None);
+
+        let points = [
+            Dvec4::new(1.0, 0.0, 0.0, 1.0),
+            Dvec4::new(0.0, 2.0, 0.0, 1.0),
+            Dvec4::new(-3.0, 0.0, 1.0, 1.0),
+            Dvec4::new(0.0, -1.0, -4.0, 1.0),
+            Dvec4::new(0.0, 2.0, 0.0, 1.0),
+        ];
+        let dirs = [
+            Dvec4::new(1.0, 0.0, 0.0, 0.0),
+            Dvec4::new(0.0, 1.0, 0.0, 0.0),
+            Dvec4::new(-1.0, 0.0, 0.0, 0.0),
+            Dvec4::new(0.0, 0.0, -1.0, 0.0),
+            Dvec4::new(1.0, 1.0, 1.0, 0.0),
+        ];
+        for dir in dirs {
+            let mut expected = 0;
+            for i in 1..points.len() {
+                if points[i].dot(dir) > points[expected].dot(dir) {
+                    expected = i;
+                }
+            }
+            assert_eq!(argmax_dot(&points, dir), Some(expected));
+        }
+
+        // Tie between the points 1 and 4
+        assert_eq!(argmax_dot(&points, Dvec4::new(0.0, 1.0, 0.0, 0.0)), Some(1));
+    }
+
+    #[test]
+    fn clamp_to_cone_works() {
+        let axis = Dvec4::direction(0.0, 0.0, 1.0);
+        let max_angle = 30f64.to_radians();
+        let at_angle = |degrees: f64| {
+            let (s, c) = degrees.to_radians().sin_cos();
+            Dvec4::direction(2.0 * s, 0.0, 2.0 * c)
+        };
+
+        let inside = at_angle(29.0);
+        assert_eq!(inside.clamp_to_cone(axis, max_angle), inside);
+
+        let outside = at_angle(31.0);
+        assert!(
+            outside
+                .clamp_to_cone(axis, max_angle)
+                .max_abs_diff(at_angle(30.0))
+                < 1e-12
+        );
+
+        let opposite = Dvec4::direction(0.0, 0.0, -2.0);
+        let clamped = opposite.clamp_to_cone(axis, max_angle);
+        assert!((clamped.norm() - 2.0).abs() < 1e-12);
+        assert!((clamped.dot3(axis) - 2.0 * max_angle.cos()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn minmax_reduce_works() {
+        assert_eq!(Dvec4::new(3.0, -1.0, 2.0, 0.5).minmax_reduce(), (-1.0, 3.0));
+
+        // The result must match the separate reductions bit for bit, including for NaN and signed zeros
+        for v in [
+            Dvec4::new(3.0, -1.0, 2.0, 0.5),
+            Dvec4::new(-0.0, 0.0, 7.0, -7.0),
+            Dvec4::new(f64::NAN, 1.0, 2.0, 3.0),
+            Dvec4::new(1.0, f64::NAN, 2.0, 3.0),
+            Dvec4::new(1.0, 2.0, f64::NAN, 3.0),
+            Dvec4::new(1.0, 2.0, 3.0, f64::NAN),
+        ] {
+            let (min, max) = v.minmax_reduce();
+            assert_eq!(min.to_bits(), v.min_reduce().to_bits());
+            assert_eq!(max.to_bits(), v.max_reduce().to_bits());
+        }
+    }
+
+    #[test]
+    fn try_from_vec_works() {
+        let v = Dvec4::new(1.0, 2.0, 3.0, 4.0);
+        assert_eq!(Dvec4::try_from_vec(&v.into_vec()), Ok(v));
+        assert_eq!(
+            Dvec4::try_from_vec(&[1.0, 2.0, 3.0]),
+            Err(LengthError {
+                expected: 4,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            Dvec4::try_from_vec(&[1.0; 5]),
+            Err(LengthError {
+                expected: 4,
+                actual: 5
+            })
+        );
+    }
+
+    #[test]
+    fn ulps_eq_works() {
+        let tiny = f64::from_bits(1);
+        assert!(ulps_eq_f64(0.0, -0.0, 0));
+        assert!(ulps_eq_f64(tiny, -tiny, 2));
+        assert!(!ulps_eq_f64(tiny, -tiny, 1));
+        assert!(ulps_eq_f64(1.0, 1.0 + f64::EPSILON, 1));
+        assert!(!ulps_eq_f64(1.0, 1.0 + 2.0 * f64::EPSILON, 1));
+        assert!(!ulps_eq_f64(f64::MAX, f64::NEG_INFINITY, u32::MAX));
+        assert!(ulps_eq_f64(f64::INFINITY, f64::INFINITY, 0));
+    }
+
+    #[test]
+    fn fma_accumulator_works() {
+        assert_eq!(FmaAccumulator::new().finish(), Dvec4::splat(0.0));
+
+        // x * x - 1 where the rounding of x * x would lose the lowest bits
+        let x = 1.0 + 2.0f64.powi(-30);
+        let exact = 2.0f64.powi(-29) + 2.0f64.powi(-60);
+        let mut acc = FmaAccumulator::new();
+        acc.mul_add(-1.0, Dvec4::new(1.0, 1.0, 2.0, 0.0));
+        acc.mul_add(x, Dvec4::new(x, 1.0, 2.0, 3.0));
+        let result = acc.finish();
+        assert_eq!(result, Dvec4::new(exact, x - 1.0, 2.0 * x - 2.0, 3.0 * x));
+
+        let naive = Dvec4::new(1.0, 1.0, 2.0, 0.0) * -1.0 + Dvec4::new(x, 1.0, 2.0, 3.0) * x;
+        assert_eq!(naive[0], 2.0f64.powi(-29));
+    }
+
+    #[test]
+    fn running_stats_works() {
+        let values = [
+            Dvec4::new(1.0, 10.0, 5.0, 1.0),
+            Dvec4::new(2.0, 20.0, 6.0, 1.0),
+            Dvec4::new(3.0, 30.0, 7.0, 1.0),
+            Dvec4::new(4.0, 40.0, 6.0, 1.0),
+        ];
+        let mean = Dvec4::new(2.5, 25.0, 6.0, 1.0);
+        let variance = Dvec4::new(1.25, 125.0, 0.5, 0.0);
+
+        let mut stats = RunningStats4::new();
+        for v in values {
+            stats.push(v);
+        }
+        assert_eq!(stats.count(), 4);
+        assert_eq!(stats.mean(), mean);
+        assert_eq!(stats.variance(), variance);
+
+        // A large offset does not destroy the variance
+        let mut offset_stats = RunningStats4::new();
+        for v in values {
+            offset_stats.push(v + 1e9);
+        }
+        assert!(offset_stats.variance().max_abs_diff(variance) < 1e-5);
+        assert!(offset_stats.std_dev().max_abs_diff(variance.map(f64::sqrt)) < 1e-5);
+    }
+
+    #[test]
+    fn clip_segment_to_plane_works() {
+        let plane = Dvec4::new(0.0, 1.0, 0.0, -1.0); // Keep y >= 1
+        let inside = (Dvec4::point(0.0, 2.0, 0.0), Dvec4::point(1.0, 3.0, 0.0));
+        assert_eq!(
+            clip_segment_to_plane(inside.0, inside.1, plane),
+            Some(inside)
+        );
+
+        let outside = (Dvec4::point(0.0, 0.0, 0.0), Dvec4::point(1.0, -3.0, 0.0));
+        assert_eq!(clip_segment_to_plane(outside.0, outside.1, plane), None);
+
+        let a = Dvec4::point(0.0, 0.0, 0.0);
+        let b = Dvec4::point(2.0, 4.0, 0.0);
+        let crossing = Dvec4::point(0.5, 1.0, 0.0);
+        let (p, q) = clip_segment_to_plane(a, b, plane).unwrap();
+        assert!(p.max_abs_diff(crossing) < 1e-12);
+        assert_eq!(q, b);
+        let (p, q) = clip_segment_to_plane(b, a, plane).unwrap();
+        assert_eq!(p, b);
+        assert!(q.max_abs_diff(crossing) < 1e-12);
+    }
+
+    #[test]
+    fn kahan_sum_works() {
+        let mut kahan = KahanSum4::new();
+        let mut naive = Dvec4::splat(0.0);
+        let large = Dvec4::new(1.0, 1e8, -1.0, 0.0);
+        let small = Dvec4::new(1e-16, 1e-9, 1e-16, 0.1);
+        kahan.add(large);
+        naive += large;
+        for _ in 0..1_000_000 {
+            kahan.add(small);
+            naive += small;
+        }
+
+        let exact = Dvec4::new(1.0 + 1e-10, 1e8 + 1e-3, -1.0 + 1e-10, 1e5);
+        let kahan_error = (kahan.sum() - exact).abs().max_reduce();
+        let naive_error = (naive - exact).abs().max_reduce();
+        assert!(kahan_error < 1e-9);
+        assert!(naive_error > 1e-7);
+        assert!(kahan_error * 100.0 < naive_error);
+    }
+
+    #[test]
+    fn clamp_in_basis_works() {
+        let min = Dvec4::new(-1.0, -2.0, -3.0, 0.0);
+        let max = Dvec4::new(1.0, 2.0, 3.0, 0.0);
+        let v = Dvec4::new(5.0, 5.0, -1.0, 0.0);
+        assert_eq!(
+            v.clamp_in_basis(Dmat4::identity(), min, max),
+            v.clamp(min, max)
+        );
+
+        // The local x axis is the world y axis, and the local y axis is the world -x axis
+        let basis = Dmat4::from_rotation_z(std::f64::consts::FRAC_PI_2);
+        let clamped = v.clamp_in_basis(basis, min, max);
+        assert!(clamped.max_abs_diff(Dvec4::new(2.0, 1.0, -1.0, 0.0)) < 1e-12);
+        let inside = Dvec4::new(-1.5, 0.5, 2.0, 0.0);
+        assert!(inside.clamp_in_basis(basis, min, max).max_abs_diff(inside) < 1e-12);
+    }
 }
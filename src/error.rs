@@ -0,0 +1,22 @@
+use std::fmt;
+
+/// Error returned when converting from a slice that does not have the expected number of elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthError {
+    /// Number of elements required by the target type.
+    pub expected: usize,
+    /// Number of elements that were provided.
+    pub actual: usize,
+}
+
+impl fmt::Display for LengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} elements, got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for LengthError {}
@@ -156,4 +156,28 @@ impl Mat4<f32, Fvec4> for Fmat4 {
     }
 }
 
+impl Fmat4 {
+    /// Bytes of this matrix in the std140 layout of a `mat4`, i.e. four columns of 16 bytes.
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn to_std140(&self) -> [u8; 64] {
+        bytemuck::cast(*self)
+    }
+}
+
 implement_matops!(Fmat4, Fvec4, f32);
+
+#[cfg(all(test, feature = "bytemuck"))]
+mod tests {
+    use super::*;
+    use crate::Vec4;
+
+    #[test]
+    fn to_std140_works() {
+        let m = Fmat4::from_translation(Fvec4::new(5.0, 6.0, 7.0, 0.0));
+        let bytes = m.to_std140();
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(bytes[48..52], 5.0f32.to_ne_bytes());
+        assert_eq!(bytemuck::cast::<_, Fmat4>(bytes), m);
+    }
+}
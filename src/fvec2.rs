@@ -1,4 +1,4 @@
-use crate::Vec2;
+use crate::{ulps_eq_f32, Vec2, Vector};
 
 /// 2D vector with single precision.
 ///
@@ -10,7 +10,7 @@ use crate::Vec2;
 /// ## Examples
 ///
 /// ```
-/// use mafs::{Vec2, Fvec2};
+/// use mafs::{Vec2, Vector, Fvec2};
 ///
 /// // Construction
 /// let a = Fvec2::new(2.0, 3.0);
@@ -53,20 +53,20 @@ impl std::fmt::Debug for Fvec2 {
     }
 }
 
-impl Vec2<f32> for Fvec2 {
+impl Vector<f32> for Fvec2 {
     #[inline]
-    fn new(x: f32, y: f32) -> Fvec2 {
-        Fvec2 { inner: [x, y] }
+    fn splat(value: f32) -> Fvec2 {
+        Fvec2 { inner: [value; 2] }
     }
 
     #[inline]
-    fn as_array(&self) -> &[f32; 2] {
-        &self.inner
+    fn as_slice(&self) -> &[f32] {
+        self.as_array()
     }
 
     #[inline]
-    fn as_mut_array(&mut self) -> &mut [f32; 2] {
-        &mut self.inner
+    fn as_mut_slice(&mut self) -> &mut [f32] {
+        self.as_mut_array()
     }
 
     #[inline]
@@ -124,6 +124,68 @@ impl Vec2<f32> for Fvec2 {
         }
     }
 
+    #[inline]
+    fn round(&self) -> Fvec2 {
+        Fvec2 {
+            inner: [
+                self.inner[0].round_ties_even(),
+                self.inner[1].round_ties_even(),
+            ],
+        }
+    }
+
+    #[inline]
+    fn abs(&self) -> Fvec2 {
+        Fvec2 {
+            inner: [self.inner[0].abs(), self.inner[1].abs()],
+        }
+    }
+
+    #[inline]
+    fn signum_nonzero(&self) -> Fvec2 {
+        self.map(|x| if x < 0.0 { -1.0 } else { 1.0 })
+    }
+
+    #[inline]
+    fn mul_add(&self, mul: Fvec2, add: Fvec2) -> Fvec2 {
+        Fvec2 {
+            inner: [
+                self.inner[0].mul_add(mul.inner[0], add.inner[0]),
+                self.inner[1].mul_add(mul.inner[1], add.inner[1]),
+            ],
+        }
+    }
+
+    #[inline]
+    fn mul_sub(&self, mul: Fvec2, sub: Fvec2) -> Fvec2 {
+        Fvec2 {
+            inner: [
+                self.inner[0].mul_add(mul.inner[0], -sub.inner[0]),
+                self.inner[1].mul_add(mul.inner[1], -sub.inner[1]),
+            ],
+        }
+    }
+
+    #[inline]
+    fn neg_mul_add(&self, mul: Fvec2, add: Fvec2) -> Fvec2 {
+        Fvec2 {
+            inner: [
+                (-self.inner[0]).mul_add(mul.inner[0], add.inner[0]),
+                (-self.inner[1]).mul_add(mul.inner[1], add.inner[1]),
+            ],
+        }
+    }
+
+    #[inline]
+    fn neg_mul_sub(&self, mul: Fvec2, sub: Fvec2) -> Fvec2 {
+        Fvec2 {
+            inner: [
+                (-self.inner[0]).mul_add(mul.inner[0], -sub.inner[0]),
+                (-self.inner[1]).mul_add(mul.inner[1], -sub.inner[1]),
+            ],
+        }
+    }
+
     #[inline]
     fn min_reduce(&self) -> f32 {
         self.inner[0].min(self.inner[1])
@@ -134,16 +196,142 @@ impl Vec2<f32> for Fvec2 {
         self.inner[0].max(self.inner[1])
     }
 
+    #[inline]
+    fn minmax_reduce(&self) -> (f32, f32) {
+        (self.min_reduce(), self.max_reduce())
+    }
+
     #[inline]
     fn eq_reduce(&self, rhs: Fvec2) -> bool {
         self.inner[0] == rhs.inner[0] && self.inner[1] == rhs.inner[1]
     }
 
+    #[inline]
+    fn ulps_eq(&self, rhs: Fvec2, max_ulps: u32) -> bool {
+        self.as_slice()
+            .iter()
+            .zip(rhs.as_slice())
+            .all(|(&a, &b)| ulps_eq_f32(a, b, max_ulps))
+    }
+
     #[inline]
     fn dot(&self, rhs: Fvec2) -> f32 {
         self.inner[0] * rhs.inner[0] + self.inner[1] * rhs.inner[1]
     }
+
+    #[inline]
+    fn nan_to_num(&self, nan: f32, pos_inf: f32, neg_inf: f32) -> Fvec2 {
+        self.map(|x| match x {
+            x if x.is_nan() => nan,
+            f32::INFINITY => pos_inf,
+            f32::NEG_INFINITY => neg_inf,
+            x => x,
+        })
+    }
+}
+
+impl Vec2<f32> for Fvec2 {
+    #[inline]
+    fn new(x: f32, y: f32) -> Fvec2 {
+        Fvec2 { inner: [x, y] }
+    }
+
+    #[inline]
+    fn as_array(&self) -> &[f32; 2] {
+        &self.inner
+    }
+
+    #[inline]
+    fn as_mut_array(&mut self) -> &mut [f32; 2] {
+        &mut self.inner
+    }
+
+    #[inline]
+    fn flip_axes(&self, mask: [bool; 2]) -> Fvec2 {
+        let flip = |x: f32, flip: bool| f32::from_bits(x.to_bits() ^ ((flip as u32) << 31));
+        Fvec2 {
+            inner: [flip(self.inner[0], mask[0]), flip(self.inner[1], mask[1])],
+        }
+    }
+}
+
+impl Fvec2 {
+    /// Same as [`Dvec4::to_bits`](crate::Dvec4::to_bits), see [`f32::to_bits`].
+    ///
+    /// ```
+    /// use mafs::{Vec2, Fvec2};
+    ///
+    /// let v = Fvec2::new(-0.0, f32::NAN);
+    /// assert_eq!(Fvec2::from_bits(v.to_bits()).to_bits(), v.to_bits());
+    /// assert_eq!(v.to_bits()[0], 1 << 31);
+    /// ```
+    #[inline]
+    pub fn to_bits(&self) -> [u32; 2] {
+        self.as_array().map(f32::to_bits)
+    }
+
+    /// Create a vector from the raw IEEE 754 representation of each component, see [`f32::from_bits`].
+    #[inline]
+    pub fn from_bits(bits: [u32; 2]) -> Fvec2 {
+        let [x, y] = bits.map(f32::from_bits);
+        Fvec2::new(x, y)
+    }
+
+    /// Bytes of this vector in the std140 layout of a `vec2`, i.e. 8 bytes with an alignment of 8.
+    /// Inside an array, use [`Fvec2::to_std140_array_element`] instead.
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn to_std140(&self) -> [u8; 8] {
+        bytemuck::cast(*self)
+    }
+
+    /// Bytes of this vector as an element of a `vec2` array in the std140 layout,
+    /// where each element is aligned to 16 bytes: the 8 bytes of [`Fvec2::to_std140`] followed by 8 bytes of padding.
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn to_std140_array_element(&self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&self.to_std140());
+        bytes
+    }
 }
 
 implement_scalarops!(Fvec2, f32);
 implement_vecops!(Fvec2, f32);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minmax_reduce_works() {
+        assert_eq!(Fvec2::new(3.0, -1.0).minmax_reduce(), (-1.0, 3.0));
+
+        // The result must match the separate reductions bit for bit, including for NaN and signed zeros
+        for v in [
+            Fvec2::new(3.0, -1.0),
+            Fvec2::new(-0.0, 0.0),
+            Fvec2::new(f32::NAN, 1.0),
+            Fvec2::new(1.0, f32::NAN),
+        ] {
+            let (min, max) = v.minmax_reduce();
+            assert_eq!(min.to_bits(), v.min_reduce().to_bits());
+            assert_eq!(max.to_bits(), v.max_reduce().to_bits());
+        }
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn to_std140_works() {
+        let v = Fvec2::new(1.5, -2.0);
+        let bytes = v.to_std140();
+        assert_eq!(bytes.len(), 8);
+        assert_eq!(bytes[..4], 1.5f32.to_ne_bytes());
+        assert_eq!(bytemuck::cast::<_, Fvec2>(bytes), v);
+
+        let element = v.to_std140_array_element();
+        assert_eq!(element.len(), 16);
+        assert_eq!(element[..8], bytes);
+        assert_eq!(element[8..], [0; 8]);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Vec4;
+use crate::{Fvec2, Vec2, Vec4, Vector};
 use std::arch::x86_64::*;
 
 /// 4D vector with single precision
@@ -8,7 +8,7 @@ use std::arch::x86_64::*;
 /// ## Examples
 ///
 /// ```
-/// use mafs::{Vec4, Fvec4};
+/// use mafs::{Vec4, Vector, Fvec4};
 ///
 /// // Construction
 /// let a = Fvec4::new(2.0, 3.0, 5.0, 6.0);
@@ -59,24 +59,24 @@ impl std::fmt::Debug for Fvec4 {
     }
 }
 
-impl Vec4<f32> for Fvec4 {
+impl Vector<f32> for Fvec4 {
     #[inline]
-    fn new(x: f32, y: f32, z: f32, w: f32) -> Fvec4 {
+    fn splat(value: f32) -> Fvec4 {
         unsafe {
             Fvec4 {
-                inner: _mm_set_ps(w, z, y, x),
+                inner: _mm_set1_ps(value),
             }
         }
     }
 
     #[inline]
-    fn as_array(&self) -> &[f32; 4] {
-        unsafe { &*(self as *const Fvec4 as *const [f32; 4]) }
+    fn as_slice(&self) -> &[f32] {
+        self.as_array()
     }
 
     #[inline]
-    fn as_mut_array(&mut self) -> &mut [f32; 4] {
-        unsafe { &mut *(self as *mut Fvec4 as *mut [f32; 4]) }
+    fn as_mut_slice(&mut self) -> &mut [f32] {
+        self.as_mut_array()
     }
 
     #[inline]
@@ -142,6 +142,72 @@ impl Vec4<f32> for Fvec4 {
         }
     }
 
+    #[inline]
+    fn round(&self) -> Fvec4 {
+        unsafe {
+            Fvec4 {
+                inner: _mm_round_ps::<{ _MM_FROUND_TO_NEAREST_INT | _MM_FROUND_NO_EXC }>(
+                    self.inner,
+                ),
+            }
+        }
+    }
+
+    #[inline]
+    fn abs(&self) -> Fvec4 {
+        unsafe {
+            Fvec4 {
+                inner: _mm_andnot_ps(_mm_set1_ps(-0.0), self.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn signum_nonzero(&self) -> Fvec4 {
+        unsafe {
+            let negative = _mm_cmplt_ps(self.inner, _mm_setzero_ps());
+            Fvec4 {
+                inner: _mm_blendv_ps(_mm_set1_ps(1.0), _mm_set1_ps(-1.0), negative),
+            }
+        }
+    }
+
+    #[inline]
+    fn mul_add(&self, mul: Fvec4, add: Fvec4) -> Fvec4 {
+        unsafe {
+            Fvec4 {
+                inner: _mm_fmadd_ps(self.inner, mul.inner, add.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn mul_sub(&self, mul: Fvec4, sub: Fvec4) -> Fvec4 {
+        unsafe {
+            Fvec4 {
+                inner: _mm_fmsub_ps(self.inner, mul.inner, sub.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn neg_mul_add(&self, mul: Fvec4, add: Fvec4) -> Fvec4 {
+        unsafe {
+            Fvec4 {
+                inner: _mm_fnmadd_ps(self.inner, mul.inner, add.inner),
+            }
+        }
+    }
+
+    #[inline]
+    fn neg_mul_sub(&self, mul: Fvec4, sub: Fvec4) -> Fvec4 {
+        unsafe {
+            Fvec4 {
+                inner: _mm_fnmsub_ps(self.inner, mul.inner, sub.inner),
+            }
+        }
+    }
+
     #[inline]
     fn min_reduce(&self) -> f32 {
         unsafe {
@@ -160,15 +226,36 @@ impl Vec4<f32> for Fvec4 {
         }
     }
 
+    #[inline]
+    fn minmax_reduce(&self) -> (f32, f32) {
+        unsafe {
+            let perm = _mm_permute_ps::<0b_11_10>(self.inner);
+            // Minimums in the low half, maximums in the high half
+            let both = _mm_movelh_ps(_mm_min_ps(self.inner, perm), _mm_max_ps(self.inner, perm));
+            let swapped = _mm_permute_ps::<0b_10_11_00_01>(both);
+            let min = _mm_min_ps(both, swapped);
+            let max = _mm_max_ps(both, swapped);
+            (_mm_cvtss_f32(min), _mm_cvtss_f32(_mm_movehl_ps(max, max)))
+        }
+    }
+
     #[inline]
     fn eq_reduce(&self, rhs: Fvec4) -> bool {
         unsafe {
             let mask = _mm_cmpeq_ps(self.inner, rhs.inner);
-            let reduce = _mm_movemask_epi8(std::mem::transmute(mask));
+            let reduce = _mm_movemask_epi8(_mm_castps_si128(mask));
             reduce == 0xffff
         }
     }
 
+    #[inline]
+    fn ulps_eq(&self, rhs: Fvec4, max_ulps: u32) -> bool {
+        self.as_slice()
+            .iter()
+            .zip(rhs.as_slice())
+            .all(|(&a, &b)| ulps_eq_f32(a, b, max_ulps))
+    }
+
     #[inline]
     fn dot(&self, rhs: Fvec4) -> f32 {
         unsafe {
@@ -179,6 +266,41 @@ impl Vec4<f32> for Fvec4 {
         }
     }
 
+    #[inline]
+    fn nan_to_num(&self, nan: f32, pos_inf: f32, neg_inf: f32) -> Fvec4 {
+        unsafe {
+            // NaN is the only value that is unordered with itself
+            let nan_mask = _mm_cmpunord_ps(self.inner, self.inner);
+            let pos_mask = _mm_cmpeq_ps(self.inner, _mm_set1_ps(f32::INFINITY));
+            let neg_mask = _mm_cmpeq_ps(self.inner, _mm_set1_ps(f32::NEG_INFINITY));
+            let mut result = _mm_blendv_ps(self.inner, _mm_set1_ps(nan), nan_mask);
+            result = _mm_blendv_ps(result, _mm_set1_ps(pos_inf), pos_mask);
+            result = _mm_blendv_ps(result, _mm_set1_ps(neg_inf), neg_mask);
+            Fvec4 { inner: result }
+        }
+    }
+}
+
+impl Vec4<f32> for Fvec4 {
+    #[inline]
+    fn new(x: f32, y: f32, z: f32, w: f32) -> Fvec4 {
+        unsafe {
+            Fvec4 {
+                inner: _mm_set_ps(w, z, y, x),
+            }
+        }
+    }
+
+    #[inline]
+    fn as_array(&self) -> &[f32; 4] {
+        unsafe { &*(self as *const Fvec4 as *const [f32; 4]) }
+    }
+
+    #[inline]
+    fn as_mut_array(&mut self) -> &mut [f32; 4] {
+        unsafe { &mut *(self as *mut Fvec4 as *mut [f32; 4]) }
+    }
+
     #[inline]
     fn cross(&self, rhs: Fvec4) -> Self {
         unsafe {
@@ -189,10 +311,259 @@ impl Vec4<f32> for Fvec4 {
             Fvec4 { inner: result }
         }
     }
+
+    #[inline]
+    fn flip_axes(&self, mask: [bool; 4]) -> Fvec4 {
+        let sign = |flip: bool| if flip { -0.0 } else { 0.0 };
+        unsafe {
+            let signs = _mm_set_ps(sign(mask[3]), sign(mask[2]), sign(mask[1]), sign(mask[0]));
+            Fvec4 {
+                inner: _mm_xor_ps(self.inner, signs),
+            }
+        }
+    }
+
+    #[inline]
+    fn min_reduce3(&self) -> f32 {
+        unsafe {
+            let inf = _mm_set1_ps(f32::INFINITY);
+            Fvec4 {
+                inner: _mm_blend_ps::<0b_1000>(self.inner, inf),
+            }
+            .min_reduce()
+        }
+    }
+
+    #[inline]
+    fn max_reduce3(&self) -> f32 {
+        unsafe {
+            let neg_inf = _mm_set1_ps(f32::NEG_INFINITY);
+            Fvec4 {
+                inner: _mm_blend_ps::<0b_1000>(self.inner, neg_inf),
+            }
+            .max_reduce()
+        }
+    }
+
+    #[inline]
+    fn dot_fma(&self, rhs: Fvec4) -> f32 {
+        unsafe {
+            let prod = _mm_mul_ps(self.inner, rhs.inner);
+            let reduce64 = _mm_fmadd_ps(
+                _mm_movehl_ps(self.inner, self.inner),
+                _mm_movehl_ps(rhs.inner, rhs.inner),
+                prod,
+            );
+            let reduce32 = _mm_add_ss(reduce64, _mm_permute_ps::<1>(reduce64));
+            _mm_cvtss_f32(reduce32)
+        }
+    }
+}
+
+impl Fvec4 {
+    /// Approximate norm of this vector, using the reciprocal square root instruction
+    /// refined with one Newton-Raphson step.
+    ///
+    /// The relative error is around `2^-22`. A zero vector has a zero norm, not NaN.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Fvec4};
+    ///
+    /// assert!((Fvec4::new(3.0, 4.0, 0.0, 0.0).norm_fast() - 5.0).abs() < 1e-4);
+    /// assert_eq!(Fvec4::new(0.0, 0.0, 0.0, 0.0).norm_fast(), 0.0);
+    /// ```
+    #[inline]
+    pub fn norm_fast(&self) -> f32 {
+        let dot = self.dot(*self);
+        if dot == 0.0 {
+            return 0.0;
+        }
+        let y = unsafe { _mm_cvtss_f32(_mm_rsqrt_ss(_mm_set_ss(dot))) };
+        let y = y * (1.5 - 0.5 * dot * y * y);
+        dot * y
+    }
+
+    /// Quantize a color into four bytes, packed as RGBA8: `x` is in the low byte and `w` is in the high byte.
+    /// Each component is clamped into `[0, 1]` (NaN becomes 0), multiplied by 255, and rounded to the nearest
+    /// integer with ties to even.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Fvec4};
+    ///
+    /// assert_eq!(Fvec4::new(1.0, 0.0, 0.5, 1.0).pack_unorm8(), 0xff8000ff);
+    /// assert_eq!(Fvec4::new(2.0, -1.0, 0.2, 0.0).pack_unorm8(), 0x003300ff);
+    /// assert_eq!(Fvec4::unpack_unorm8(0x12345678).pack_unorm8(), 0x12345678);
+    /// ```
+    #[inline]
+    pub fn pack_unorm8(&self) -> u32 {
+        unsafe {
+            let clamped = _mm_min_ps(_mm_max_ps(self.inner, _mm_setzero_ps()), _mm_set1_ps(1.0));
+            let ints = _mm_cvtps_epi32(_mm_mul_ps(clamped, _mm_set1_ps(255.0)));
+            let packed16 = _mm_packus_epi32(ints, ints);
+            let packed8 = _mm_packus_epi16(packed16, packed16);
+            _mm_cvtsi128_si32(packed8) as u32
+        }
+    }
+
+    /// Inverse of [`Fvec4::pack_unorm8`]: each byte is converted to a component in `[0, 1]`.
+    #[inline]
+    pub fn unpack_unorm8(bits: u32) -> Fvec4 {
+        unsafe {
+            let ints = _mm_cvtepu8_epi32(_mm_cvtsi32_si128(bits as i32));
+            Fvec4 {
+                inner: _mm_div_ps(_mm_cvtepi32_ps(ints), _mm_set1_ps(255.0)),
+            }
+        }
+    }
+
+    /// Convert a linear color to sRGB with a fast approximation of the transfer function, made of three
+    /// square roots and a polynomial. The color is first clamped into `[0, 1]`. The alpha (`w`) is unchanged.
+    ///
+    /// The absolute error is below `1e-3` for inputs above `0.005`, but reaches a few percents for very dark
+    /// values, where the exact function is linear.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Fvec4};
+    ///
+    /// let gray = Fvec4::new(0.5, 0.5, 0.5, 0.25).to_srgb_approx();
+    /// assert!(gray.max_abs_diff(Fvec4::new(0.735, 0.735, 0.735, 0.25)) < 1e-3);
+    ///
+    /// let fixed = Fvec4::new(0.0, 1.0, 0.0, 1.0);
+    /// assert!(fixed.to_srgb_approx().max_abs_diff(fixed) < 1e-6);
+    /// assert!(fixed.from_srgb_approx().max_abs_diff(fixed) < 1e-6);
+    /// assert!(gray.from_srgb_approx().max_abs_diff(Fvec4::new(0.5, 0.5, 0.5, 0.25)) < 2e-3);
+    /// ```
+    #[inline]
+    pub fn to_srgb_approx(&self) -> Fvec4 {
+        unsafe {
+            let c = _mm_min_ps(_mm_max_ps(self.inner, _mm_setzero_ps()), _mm_set1_ps(1.0));
+            let s1 = _mm_sqrt_ps(c);
+            let s2 = _mm_sqrt_ps(s1);
+            let s3 = _mm_sqrt_ps(s2);
+            let mut result = _mm_mul_ps(_mm_set1_ps(0.662_002_7), s1);
+            result = _mm_fmadd_ps(_mm_set1_ps(0.684_122_1), s2, result);
+            result = _mm_fnmadd_ps(_mm_set1_ps(0.323_583_6), s3, result);
+            result = _mm_fnmadd_ps(_mm_set1_ps(0.022_541_147), c, result);
+            Fvec4 {
+                inner: _mm_blend_ps::<0b_10_00>(result, self.inner),
+            }
+        }
+    }
+
+    /// Convert an sRGB color to linear with a fast polynomial approximation of the transfer function.
+    /// The color is first clamped into `[0, 1]`. The alpha (`w`) is unchanged.
+    /// The absolute error is below `2e-3`.
+    #[inline]
+    pub fn from_srgb_approx(&self) -> Fvec4 {
+        unsafe {
+            let s = _mm_min_ps(_mm_max_ps(self.inner, _mm_setzero_ps()), _mm_set1_ps(1.0));
+            let mut result = _mm_fmadd_ps(s, _mm_set1_ps(0.305_306_01), _mm_set1_ps(0.682_171_1));
+            result = _mm_fmadd_ps(s, result, _mm_set1_ps(0.012_522_878));
+            result = _mm_mul_ps(s, result);
+            Fvec4 {
+                inner: _mm_blend_ps::<0b_10_00>(result, self.inner),
+            }
+        }
+    }
+
+    /// Encode an HDR color (`xyz`) to RGBM, so that it can be stored in an 8-bit RGBA texture.
+    /// The color is divided by `max_range`, the brightest value that can be represented, then by a shared
+    /// multiplier stored in `w`. The multiplier is rounded up to a multiple of `1/255` so that it survives
+    /// the 8-bit storage, and is at least `1/255`.
+    ///
+    /// The input `w` is ignored. The components of the result are in `[0, 1]`: negative components become zero,
+    /// and a component brighter than `max_range` is clamped, which changes the hue of the color.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Fvec4};
+    ///
+    /// for color in [
+    ///     Fvec4::new(0.2, 0.5, 0.9, 1.0),
+    ///     Fvec4::new(3.5, 1.2, 0.1, 1.0),
+    ///     Fvec4::new(5.0, 5.5, 6.0, 1.0),
+    /// ] {
+    ///     let rgbm = color.encode_rgbm(6.0);
+    ///     assert!(rgbm.max_reduce() <= 1.0 && rgbm.min_reduce() >= 0.0);
+    ///     let decoded = rgbm.decode_rgbm(6.0);
+    ///     assert!((decoded - color).abs().max_reduce3() < 1e-5 * color.max_reduce3());
+    /// }
+    ///
+    /// let clamped = Fvec4::new(12.0, 3.0, 0.0, 1.0).encode_rgbm(6.0).decode_rgbm(6.0);
+    /// assert!(clamped.max_abs_diff(Fvec4::new(6.0, 3.0, 0.0, 1.0)) < 1e-5);
+    /// ```
+    #[inline]
+    pub fn encode_rgbm(&self, max_range: f32) -> Fvec4 {
+        let scaled = *self / max_range;
+        let multiplier = scaled.max_reduce3().clamp(1.0 / 255.0, 1.0);
+        let multiplier = (multiplier * 255.0).ceil() / 255.0;
+        let mut result = (scaled / multiplier).clamp(Fvec4::splat(0.0), Fvec4::splat(1.0));
+        result[3] = multiplier;
+        result
+    }
+
+    /// Decode an RGBM color, the inverse of [`Fvec4::encode_rgbm`] with the same `max_range`.
+    /// The `w` of the result is 1.
+    #[inline]
+    pub fn decode_rgbm(&self, max_range: f32) -> Fvec4 {
+        let mut result = *self * (self[3] * max_range);
+        result[3] = 1.0;
+        result
+    }
+
+    /// Same as [`Dvec4::to_bits`](crate::Dvec4::to_bits), see [`f32::to_bits`].
+    ///
+    /// ```
+    /// use mafs::{Vec4, Fvec4};
+    ///
+    /// let v = Fvec4::new(1.0, -0.0, f32::NAN, f32::INFINITY);
+    /// assert_eq!(Fvec4::from_bits(v.to_bits()).to_bits(), v.to_bits());
+    /// assert_eq!(v.to_bits()[1], 1 << 31);
+    /// ```
+    #[inline]
+    pub fn to_bits(&self) -> [u32; 4] {
+        self.as_array().map(f32::to_bits)
+    }
+
+    /// Create a vector from the raw IEEE 754 representation of each component, see [`f32::from_bits`].
+    #[inline]
+    pub fn from_bits(bits: [u32; 4]) -> Fvec4 {
+        let [x, y, z, w] = bits.map(f32::from_bits);
+        Fvec4::new(x, y, z, w)
+    }
+
+    /// Assemble a vector from its two halves: `xy` in lanes 0 and 1, `zw` in lanes 2 and 3.
+    ///
+    /// ```
+    /// use mafs::{Vec2, Fvec2, Vec4, Fvec4};
+    ///
+    /// let v = Fvec4::from_parts(Fvec2::new(1.0, 2.0), Fvec2::new(3.0, 4.0));
+    /// assert_eq!(v, Fvec4::new(1.0, 2.0, 3.0, 4.0));
+    /// assert_eq!(v.split(), (Fvec2::new(1.0, 2.0), Fvec2::new(3.0, 4.0)));
+    /// ```
+    #[inline]
+    pub fn from_parts(xy: Fvec2, zw: Fvec2) -> Fvec4 {
+        Fvec4::new(xy[0], xy[1], zw[0], zw[1])
+    }
+
+    /// Split this vector into its two halves, the inverse of [`Fvec4::from_parts`].
+    #[inline]
+    pub fn split(&self) -> (Fvec2, Fvec2) {
+        let [x, y, z, w] = *self.as_array();
+        (Fvec2::new(x, y), Fvec2::new(z, w))
+    }
+
+    /// Bytes of this vector in the std140 layout of a `vec4`, i.e. 16 bytes with an alignment of 16.
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn to_std140(&self) -> [u8; 16] {
+        bytemuck::cast(*self)
+    }
 }
 
 implement_vecops!(Fvec4, f32);
 
+implement_ulps_eq!(ulps_eq_f32, f32, i32, u32);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -214,4 +585,55 @@ mod tests {
         let f = Fvec4::new(f32::NAN, f32::NAN, f32::NAN, f32::NAN);
         assert_eq!(f == f, false);
     }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn to_std140_works() {
+        let v4 = Fvec4::new(1.0, 2.0, 3.0, 4.0);
+        let bytes = v4.to_std140();
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(bytes[12..], 4.0f32.to_ne_bytes());
+        assert_eq!(bytemuck::cast::<_, Fvec4>(bytes), v4);
+    }
+
+    #[test]
+    fn minmax_reduce_works() {
+        assert_eq!(Fvec4::new(3.0, -1.0, 2.0, 0.5).minmax_reduce(), (-1.0, 3.0));
+
+        // The result must match the separate reductions bit for bit, including for NaN and signed zeros
+        for v in [
+            Fvec4::new(3.0, -1.0, 2.0, 0.5),
+            Fvec4::new(-0.0, 0.0, 7.0, -7.0),
+            Fvec4::new(f32::NAN, 1.0, 2.0, 3.0),
+            Fvec4::new(1.0, f32::NAN, 2.0, 3.0),
+            Fvec4::new(1.0, 2.0, f32::NAN, 3.0),
+            Fvec4::new(1.0, 2.0, 3.0, f32::NAN),
+        ] {
+            let (min, max) = v.minmax_reduce();
+            assert_eq!(min.to_bits(), v.min_reduce().to_bits());
+            assert_eq!(max.to_bits(), v.max_reduce().to_bits());
+        }
+    }
+
+    #[test]
+    fn dot_fma_works() {
+        // x * x - 1 where the rounding of x * x loses the lowest bits
+        let x = 1.0 + 2.0f32.powi(-13);
+        let a = Fvec4::new(-1.0, 0.0, x, 0.0);
+        let b = Fvec4::new(1.0, 0.0, x, 0.0);
+        assert_eq!(a.dot(b), 2.0f32.powi(-12));
+        assert_eq!(a.dot_fma(b), 2.0f32.powi(-12) + 2.0f32.powi(-26));
+    }
+
+    #[test]
+    fn ulps_eq_works() {
+        let tiny = f32::from_bits(1);
+        assert!(ulps_eq_f32(tiny, -tiny, 2));
+        assert!(!ulps_eq_f32(tiny, -tiny, 1));
+        assert!(ulps_eq_f32(1.0, 1.0 + f32::EPSILON, 1));
+        assert!(!ulps_eq_f32(1.0, 1.0 + 2.0 * f32::EPSILON, 1));
+
+        let a = Fvec4::splat(0.1) + Fvec4::splat(0.2);
+        assert!(a.ulps_eq(Fvec4::splat(0.3), 1));
+    }
 }
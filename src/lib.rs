@@ -25,6 +25,25 @@
 //!     - [`Fvec4`] - 4D vetcor
 //!     - [`Fmat4`] - 4x4 matrix
 //!
+//! ## Traits
+//!
+//! - [`Vec2`], [`Vec4`] and [`Mat4`] hold the methods of the data types.
+//! - [`Vector`] holds the methods shared by [`Vec2`] and [`Vec4`], to write code that works in any dimension.
+//! - [`prelude`] re-exports all of them, so that every method is in scope with a single import:
+//!
+//! ```
+//! use mafs::prelude::*;
+//! use mafs::Dvec4;
+//!
+//! assert_eq!(Dvec4::splat(2.0).dot(Dvec4::new(1.0, 0.0, 0.0, 0.0)), 2.0);
+//! ```
+//!
+//! ## Breaking changes
+//!
+//! - The methods shared by all vectors (`splat`, `dot`, `norm`, `normalize`, `min`, `max`, the reductions...)
+//!   moved from [`Vec2`] and [`Vec4`] to their supertrait [`Vector`]. Code that only imports `Vec2` or `Vec4`
+//!   must also import `Vector`, or import [`prelude`]`::*` instead.
+//!
 //! ## Available operations
 //!
 //! - Arithmetics (`+`, `-`, `*` and `/`):
@@ -32,6 +51,7 @@
 //!     - Add or subtract two matrices.
 //!     - Multiply two matrices.
 //!     - Multiply a matrix by a vector.
+//!     - Multiply a matrix by a scalar.
 //! - Methods:
 //!     - Operations on one vector: componentwise floor
 //!     - Operations on two vectors: dot product, cross product, componentwise minimum and maximum.
@@ -42,6 +62,13 @@
 //! ## Crate features
 //!
 //! - Enable the crate feature `bytemuck` to mark all vectors as *Plain Old Data*.
+//!
+//! ## Minimum supported Rust version
+//!
+//! - Rust 1.77, declared as `rust-version` in `Cargo.toml`. It is needed for `round_ties_even`.
+
+// The tests compare booleans with `assert_eq!` to exercise the comparison operators.
+#![cfg_attr(test, allow(clippy::bool_assert_comparison))]
 
 #[macro_use]
 mod private_macros;
@@ -65,7 +92,15 @@ rustflags = [\"-Ctarget-feature=+avx2,+fma\"]
 );
 
 mod traits;
-pub use traits::{Mat4, Vec2, Vec4};
+pub use traits::{ClipSpace, Mat4, Vec2, Vec4, Vector};
+
+/// All the traits of the crate, to bring their methods into scope with `use mafs::prelude::*`.
+pub mod prelude {
+    pub use crate::traits::{Mat4, Vec2, Vec4, Vector};
+}
+
+mod error;
+pub use error::*;
 
 mod dvec2;
 pub use dvec2::*;
@@ -85,6 +120,9 @@ pub use fvec2::*;
 mod fmat4;
 pub use fmat4::*;
 
+mod curves;
+pub use curves::*;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,6 +140,23 @@ mod tests {
         assert_eq!(size_of::<Dmat4>(), 128);
     }
 
+    fn normalize_all<V: Vector<f64>>(xs: &mut [V]) {
+        for x in xs.iter_mut() {
+            *x = x.normalize();
+        }
+    }
+
+    #[test]
+    fn generic_vectors() {
+        let mut xs2 = [Dvec2::new(3.0, 4.0), Dvec2::new(0.0, -2.0)];
+        normalize_all(&mut xs2);
+        assert_eq!(xs2, [Dvec2::new(0.6, 0.8), Dvec2::new(0.0, -1.0)]);
+
+        let mut xs4 = [Dvec4::new(0.0, 3.0, 0.0, 4.0), Dvec4::splat(2.0)];
+        normalize_all(&mut xs4);
+        assert_eq!(xs4, [Dvec4::new(0.0, 0.6, 0.0, 0.8), Dvec4::splat(0.5)]);
+    }
+
     #[test]
     fn aligns() {
         assert_eq!(align_of::<Fvec2>(), 4); // <- small exception here
@@ -303,6 +303,24 @@ macro_rules! implement_matops {
             }
         }
 
+        // Matrix * Scalar
+        impl std::ops::Mul<$S> for $M {
+            type Output = $M;
+
+            #[inline]
+            fn mul(self, rhs: $S) -> $M {
+                self.mul_scalar(rhs)
+            }
+        }
+
+        // Matrix *= Scalar
+        impl std::ops::MulAssign<$S> for $M {
+            #[inline]
+            fn mul_assign(&mut self, rhs: $S) {
+                *self = self.mul_scalar(rhs)
+            }
+        }
+
         // -Matrix
         impl std::ops::Neg for $M {
             type Output = $M;
@@ -334,3 +352,27 @@ macro_rules! implement_matops {
         impl crate::traits::MatOps<$S, $V> for $M {}
     };
 }
+
+macro_rules! implement_ulps_eq {
+    ($name: ident, $S: ident, $I: ident, $U: ident) => {
+        /// Compare two floats by the number of representable values between them, see [`Vector::ulps_eq`].
+        pub(crate) fn $name(a: $S, b: $S, max_ulps: u32) -> bool {
+            if a.is_nan() || b.is_nan() {
+                return false;
+            }
+            if a == b {
+                return true;
+            }
+            // Map the bit patterns to integers that are ordered like the floats, with both zeros on 0
+            let key = |x: $S| {
+                let bits = x.to_bits() as $I;
+                if bits < 0 {
+                    $I::MIN - bits
+                } else {
+                    bits
+                }
+            };
+            key(a).abs_diff(key(b)) <= $U::from(max_ulps)
+        }
+    };
+}
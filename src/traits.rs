@@ -38,31 +38,34 @@ pub trait MatOps<S, V>:
     + Sub<Self, Output = Self> + SubAssign<Self>
     + Mul<V, Output = V>
     + Mul<Self, Output = Self> + MulAssign<Self>
+    + Mul<S, Output = Self> + MulAssign<S>
     + Neg<Output = Self>
     + IndexMut<usize, Output = V>
     + PartialEq<Self>
 {}
 
-/// Methods on two-dimensional vectors.
+/// Methods shared by vectors of any dimension.
+///
+/// This is the bound to use when writing code that works for both [`Vec2`] and [`Vec4`].
 ///
 /// - `S` is the type of the vector's components.
-pub trait Vec2<S>
+pub trait Vector<S>
 where
     Self: VecOps<S>,
-    S: Float + ScalarOps<Self>,
+    S: Float,
 {
     // --------------- Required methods ---------------
 
-    /// Create a new two-dimensional vector.
-    fn new(x: S, y: S) -> Self;
+    /// Create a vector with all equal components.
+    fn splat(value: S) -> Self;
 
-    /// Convert to an array.
+    /// Convert to a slice.
     /// Can also use the indexing operator `[]`.
-    fn as_array(&self) -> &[S; 2];
+    fn as_slice(&self) -> &[S];
 
-    /// Convert to a mutable array.
-    /// Can also use the indexing operator`[]`.
-    fn as_mut_array(&mut self) -> &mut [S; 2];
+    /// Convert to a mutable slice.
+    /// Can also use the indexing operator `[]`.
+    fn as_mut_slice(&mut self) -> &mut [S];
 
     /// Add component by component.
     /// Can also use the `+` operator.
@@ -89,23 +92,114 @@ where
     /// Round down all components to an integer value.
     fn floor(&self) -> Self;
 
-    /// Smallest of the four components.
+    /// Round all components to the nearest integer value, with ties rounded to even.
+    fn round(&self) -> Self;
+
+    /// Absolute value of all components.
+    fn abs(&self) -> Self;
+
+    /// Sign of all components, as `-1` for negative values and `1` for everything else, including both zeros and NaN.
+    /// Unlike the IEEE `signum`, the result is never zero or NaN and the sign of `-0.0` is ignored.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let v = Dvec4::new(-3.0, 0.0, -0.0, 5.0);
+    /// assert_eq!(v.signum_nonzero(), Dvec4::new(-1.0, 1.0, 1.0, 1.0));
+    /// ```
+    fn signum_nonzero(&self) -> Self;
+
+    /// Fused multiply-add: `self * mul + add` for each component, with a single rounding.
+    fn mul_add(&self, mul: Self, add: Self) -> Self;
+
+    /// Fused multiply-subtract: `self * mul - sub` for each component, with a single rounding.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let (a, b, c) = (Dvec4::new(1.0, 2.0, 3.0, 4.0), Dvec4::splat(3.0), Dvec4::new(1.0, -1.0, 0.5, 0.0));
+    /// assert_eq!(a.mul_sub(b, c), a * b - c);
+    /// ```
+    fn mul_sub(&self, mul: Self, sub: Self) -> Self;
+
+    /// Fused negated multiply-add: `add - self * mul` for each component, with a single rounding.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let (a, b, c) = (Dvec4::new(1.0, 2.0, 3.0, 4.0), Dvec4::splat(3.0), Dvec4::new(1.0, -1.0, 0.5, 0.0));
+    /// assert_eq!(a.neg_mul_add(b, c), c - a * b);
+    /// ```
+    fn neg_mul_add(&self, mul: Self, add: Self) -> Self;
+
+    /// Fused negated multiply-subtract: `-(self * mul) - sub` for each component, with a single rounding.
+    fn neg_mul_sub(&self, mul: Self, sub: Self) -> Self;
+
+    /// Smallest of all the components.
     fn min_reduce(&self) -> S;
 
-    /// Largest of the four components.
+    /// Largest of all the components.
     fn max_reduce(&self) -> S;
 
+    /// Smallest and largest of all the components, computed together so that the shuffles are shared.
+    /// The result, including with NaN components, is the same as `(self.min_reduce(), self.max_reduce())`.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::new(3.0, 1.0, 4.0, 1.0).minmax_reduce(), (1.0, 4.0));
+    /// ```
+    fn minmax_reduce(&self) -> (S, S);
+
     /// Equality of a vector to another on all components.
     fn eq_reduce(&self, rhs: Self) -> bool;
 
+    /// Approximate equality on all components, allowing each pair of components to be up to `max_ulps`
+    /// representable values apart. `0.0` and `-0.0` are equal, and values of opposite signs are compared
+    /// by counting the representable values across zero. A NaN component is never equal to anything.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let a = Dvec4::splat(0.1) + Dvec4::splat(0.2);
+    /// let b = Dvec4::splat(0.3);
+    /// assert!(a != b);
+    /// assert!(a.ulps_eq(b, 1));
+    /// assert!(!Dvec4::splat(f64::NAN).ulps_eq(Dvec4::splat(f64::NAN), u32::MAX));
+    /// ```
+    fn ulps_eq(&self, rhs: Self, max_ulps: u32) -> bool;
+
     /// Dot product.
     fn dot(&self, rhs: Self) -> S;
 
+    /// Replace NaN components with `nan`, positive infinities with `pos_inf` and negative infinities with `neg_inf`.
+    /// Finite components are left unchanged.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let v = Dvec4::new(1.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY);
+    /// assert_eq!(v.nan_to_num(0.0, 1e9, -1e9), Dvec4::new(1.0, 0.0, 1e9, -1e9));
+    /// assert_eq!(v.nan_to_zero(), Dvec4::new(1.0, 0.0, 0.0, 0.0));
+    /// ```
+    fn nan_to_num(&self, nan: S, pos_inf: S, neg_inf: S) -> Self;
+
     // --------------- Provided methods ---------------
 
-    /// Create a two-dimensional vector all with equal components.
-    fn splat(value: S) -> Self {
-        Self::new(value, value)
+    /// Apply a function on each component, one at a time.
+    /// The functions that have no SIMD instruction, like [`Vector::exp2`], [`Vector::log2`], [`Vector::atan2`] and
+    /// [`Vector::hypot`], are also computed one component at a time.
+    fn map<F: Fn(S) -> S>(&self, f: F) -> Self {
+        let mut result = *self;
+        for x in result.as_mut_slice() {
+            *x = f(*x);
+        }
+        result
+    }
+
+    /// Squared norm of this vector. Cheaper than [`Vector::norm`] when comparing lengths.
+    fn norm_squared(&self) -> S {
+        self.dot(*self)
     }
 
     /// Norm of this vector.
@@ -117,85 +211,539 @@ where
     fn normalize(&self) -> Self {
         self.div(Self::splat(self.norm()))
     }
+
+    /// Whether this vector has unit length, up to `eps`.
+    /// The squared norm is compared to 1, which avoids a square root: `eps` is roughly twice the tolerance
+    /// on the norm itself.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let v = Dvec4::new(1.0, 2.0, 3.0, 4.0).normalize();
+    /// assert!(v.is_normalized(1e-12));
+    /// assert!(!(v * 1.01).is_normalized(1e-12));
+    /// ```
+    fn is_normalized(&self, eps: S) -> bool {
+        (self.norm_squared() - S::one()).abs() <= eps
+    }
+
+    /// Same as [`Vector::normalize`], but returns `fallback` when the vector is too short to be normalized,
+    /// i.e. when its squared norm is below the smallest positive normal value of `S`, or is NaN.
+    fn normalize_or(&self, fallback: Self) -> Self {
+        if self.norm_squared() >= S::min_positive_value() {
+            self.normalize()
+        } else {
+            fallback
+        }
+    }
+
+    /// Same as [`Vector::normalize_or`] with a zero vector as the fallback.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::splat(0.0).normalize_or_zero(), Dvec4::splat(0.0));
+    /// assert_eq!(Dvec4::new(0.0, 3.0, 0.0, 4.0).normalize_or_zero(), Dvec4::new(0.0, 0.6, 0.0, 0.8));
+    /// ```
+    fn normalize_or_zero(&self) -> Self {
+        self.normalize_or(Self::splat(S::zero()))
+    }
+
+    /// Same as [`Vector::nan_to_num`] with zero as the replacement for NaN and both infinities.
+    fn nan_to_zero(&self) -> Self {
+        self.nan_to_num(S::zero(), S::zero(), S::zero())
+    }
+
+    /// Scale this vector up so that its norm is at least `min`, keeping its direction.
+    /// Vectors that are already long enough are unchanged. A zero vector has no direction, so it is returned as is,
+    /// see [`Vector::clamp_length_min_or`] to choose another result.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let short = Dvec4::new(0.0, 0.3, 0.0, 0.4);
+    /// assert_eq!(short.clamp_length_min(2.0), Dvec4::new(0.0, 1.2, 0.0, 1.6));
+    ///
+    /// let long = Dvec4::new(0.0, 3.0, 0.0, 4.0);
+    /// assert_eq!(long.clamp_length_min(2.0), long);
+    ///
+    /// let zero = Dvec4::splat(0.0);
+    /// assert_eq!(zero.clamp_length_min(2.0), zero);
+    /// assert_eq!(zero.clamp_length_min_or(2.0, Dvec4::new(2.0, 0.0, 0.0, 0.0)), Dvec4::new(2.0, 0.0, 0.0, 0.0));
+    /// ```
+    fn clamp_length_min(&self, min: S) -> Self {
+        self.clamp_length_min_or(min, *self)
+    }
+
+    /// Same as [`Vector::clamp_length_min`], but returns `fallback` when this vector is zero.
+    fn clamp_length_min_or(&self, min: S, fallback: Self) -> Self {
+        let norm_squared = self.norm_squared();
+        if norm_squared == S::zero() {
+            fallback
+        } else if norm_squared < min * min {
+            *self * (min / norm_squared.sqrt())
+        } else {
+            *self
+        }
+    }
+
+    /// Orthogonal projection of this vector onto the line directed by `axis`, which does not need to be unit length.
+    /// The projection onto a zero axis is zero.
+    fn project_onto(&self, axis: Self) -> Self {
+        let axis_norm_squared = axis.norm_squared();
+        if axis_norm_squared == S::zero() {
+            Self::splat(S::zero())
+        } else {
+            axis * (self.dot(axis) / axis_norm_squared)
+        }
+    }
+
+    /// Split this vector into `(parallel, perpendicular)` parts relative to `axis`, where `parallel` is
+    /// [`Vector::project_onto`] and `perpendicular` is the rest. With a zero axis, everything is perpendicular.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let v = Dvec4::new(1.0, 2.0, 3.0, 4.0);
+    /// let axis = Dvec4::new(0.0, 2.0, 0.0, 2.0);
+    /// let (parallel, perpendicular) = v.decompose_along(axis);
+    /// assert_eq!(parallel, Dvec4::new(0.0, 3.0, 0.0, 3.0));
+    /// assert_eq!(perpendicular, Dvec4::new(1.0, -1.0, 3.0, 1.0));
+    /// assert_eq!(parallel + perpendicular, v);
+    /// assert_eq!(perpendicular.dot(axis), 0.0);
+    /// ```
+    fn decompose_along(&self, axis: Self) -> (Self, Self) {
+        let parallel = self.project_onto(axis);
+        (parallel, *self - parallel)
+    }
+
+    /// Largest absolute difference between the components of two vectors.
+    fn max_abs_diff(&self, rhs: Self) -> S {
+        self.sub_componentwise(rhs).abs().max_reduce()
+    }
+
+    /// Round all components to the nearest multiple of the corresponding component of `step`,
+    /// with ties rounded to even like [`Vector::round`]. A zero step gives NaN.
+    fn round_to_multiple(&self, step: Self) -> Self {
+        (*self / step).round() * step
+    }
+
+    /// Same as [`Vector::round_to_multiple`] with the same step for all components.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let v = Dvec4::new(0.12, 0.37, 0.5, 0.9);
+    /// assert_eq!(v.round_to_multiple_scalar(0.25), Dvec4::new(0.0, 0.25, 0.5, 1.0));
+    ///
+    /// // Ties are rounded to even
+    /// let v = Dvec4::new(0.125, 0.375, -0.125, -0.375);
+    /// assert_eq!(v.round_to_multiple_scalar(0.25), Dvec4::new(0.0, 0.5, 0.0, -0.5));
+    /// ```
+    fn round_to_multiple_scalar(&self, step: S) -> Self {
+        self.round_to_multiple(Self::splat(step))
+    }
+
+    /// Wrap each component into `[0, period)`, with the corresponding component of `period`:
+    /// `self - (self / period).floor() * period`. This is the same as `rem_euclid` for positive periods.
+    /// Exact multiples of the period give zero.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let v = Dvec4::new(-0.25, 1.25, 2.0, 3.75);
+    /// assert_eq!(v.wrap(Dvec4::splat(1.0)), Dvec4::new(0.75, 0.25, 0.0, 0.75));
+    /// assert_eq!(v.wrap_scalar(2.0), Dvec4::new(1.75, 1.25, 0.0, 1.75));
+    /// ```
+    fn wrap(&self, period: Self) -> Self {
+        *self - (*self / period).floor() * period
+    }
+
+    /// Same as [`Vector::wrap`] with the same period for all components.
+    fn wrap_scalar(&self, period: S) -> Self {
+        self.wrap(Self::splat(period))
+    }
+
+    /// Clamp each component between the corresponding components of `min` and `max`.
+    fn clamp(&self, min: Self, max: Self) -> Self {
+        self.max_componentwise(min).min_componentwise(max)
+    }
+
+    /// Add two vectors, then clamp each component of the sum to at most `max`.
+    /// The clamp is applied after the addition, not to each operand.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let mut color = Dvec4::new(0.5, 0.25, 0.0, 1.0);
+    /// color = color.add_clamped(Dvec4::new(0.75, 0.25, 0.5, 0.0), 1.0);
+    /// assert_eq!(color, Dvec4::new(1.0, 0.5, 0.5, 1.0));
+    /// assert_eq!(color.clamped_to_zero_add(Dvec4::splat(-0.75)), Dvec4::new(0.25, 0.0, 0.0, 0.25));
+    /// ```
+    fn add_clamped(&self, rhs: Self, max: S) -> Self {
+        (*self + rhs).min_componentwise(Self::splat(max))
+    }
+
+    /// Add two vectors, then clamp each component of the sum to at least zero.
+    /// The clamp is applied after the addition, not to each operand.
+    fn clamped_to_zero_add(&self, rhs: Self) -> Self {
+        (*self + rhs).max_componentwise(Self::splat(S::zero()))
+    }
+
+    /// Snap a point inside the axis-aligned box whose corners are `min` and `max`.
+    /// This is the same as [`Vector::clamp`].
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let min = Dvec4::point(0.0, 0.0, 0.0);
+    /// let max = Dvec4::point(1.0, 2.0, 3.0);
+    ///
+    /// let outside = Dvec4::point(4.0, 1.0, -4.0);
+    /// assert_eq!(outside.clamp_to_aabb(min, max), Dvec4::point(1.0, 1.0, 0.0));
+    /// assert_eq!(outside.distance_to_aabb(min, max), 5.0);
+    ///
+    /// let inside = Dvec4::point(0.5, 1.0, 2.0);
+    /// assert_eq!(inside.clamp_to_aabb(min, max), inside);
+    /// assert_eq!(inside.distance_to_aabb(min, max), 0.0);
+    /// ```
+    fn clamp_to_aabb(&self, min: Self, max: Self) -> Self {
+        self.clamp(min, max)
+    }
+
+    /// Closest point to `self` inside the axis-aligned box whose corners are `min` and `max`.
+    /// This is the same as [`Vector::clamp_to_aabb`].
+    fn closest_point_on_aabb(&self, min: Self, max: Self) -> Self {
+        self.clamp_to_aabb(min, max)
+    }
+
+    /// Distance from a point to the axis-aligned box whose corners are `min` and `max`.
+    /// This is zero if the point is inside the box.
+    fn distance_to_aabb(&self, min: Self, max: Self) -> S {
+        (*self - self.clamp_to_aabb(min, max)).norm()
+    }
+
+    /// Compute `2^x` for each component.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::splat(3.0).exp2(), Dvec4::splat(8.0));
+    /// ```
+    fn exp2(&self) -> Self {
+        self.map(S::exp2)
+    }
+
+    /// Compute the base 2 logarithm of each component.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::splat(8.0).log2(), Dvec4::splat(3.0));
+    /// ```
+    fn log2(&self) -> Self {
+        self.map(S::log2)
+    }
+
+    /// Compute the four-quadrant arctangent of `self[i] / x[i]` for each component, see [`Float::atan2`].
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    /// use std::f64::consts::FRAC_PI_4;
+    ///
+    /// assert_eq!(Dvec4::splat(1.0).atan2(Dvec4::splat(1.0)), Dvec4::splat(FRAC_PI_4));
+    /// ```
+    fn atan2(&self, x: Self) -> Self {
+        let mut result = *self;
+        for (y, x) in result.as_mut_slice().iter_mut().zip(x.as_slice()) {
+            *y = y.atan2(*x);
+        }
+        result
+    }
+
+    /// Compute `sqrt(self[i]^2 + rhs[i]^2)` for each component, without intermediate overflow or underflow,
+    /// see [`Float::hypot`].
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let big = 2f64.powi(600); // Squaring this overflows
+    /// let x = Dvec4::new(3.0, 4.0, 3.0 * big, 0.0);
+    /// let y = Dvec4::new(4.0, 3.0, 4.0 * big, 0.0);
+    /// assert_eq!(x.hypot(y), Dvec4::new(5.0, 5.0, 5.0 * big, 0.0));
+    /// ```
+    fn hypot(&self, rhs: Self) -> Self {
+        let mut result = *self;
+        for (x, y) in result.as_mut_slice().iter_mut().zip(rhs.as_slice()) {
+            *x = x.hypot(*y);
+        }
+        result
+    }
+
+    /// Linear interpolation between two vectors: `t == 0` gives `self` and `t == 1` gives `rhs`.
+    /// Values of `t` outside of `[0, 1]` extrapolate.
+    fn lerp(&self, rhs: Self, t: S) -> Self {
+        *self * (S::one() - t) + rhs * t
+    }
+
+    /// Same as [`Vector::lerp`], but `t` is first clamped into `[0, 1]`.
+    /// The result differs from `lerp` only when `t` is outside of the unit interval.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let a = Dvec4::new(1.0, 2.0, 3.0, 4.0);
+    /// let b = Dvec4::new(5.0, 6.0, 7.0, 8.0);
+    /// assert_eq!(a.lerp_clamped(b, -0.5), a);
+    /// assert_eq!(a.lerp_clamped(b, 2.0), b);
+    /// assert_eq!(a.lerp_clamped(b, 0.5), a.lerp(b, 0.5));
+    /// ```
+    fn lerp_clamped(&self, rhs: Self, t: S) -> Self {
+        self.lerp(rhs, t.max(S::zero()).min(S::one()))
+    }
+
+    /// Map each component affinely from `[in_min, in_max]` to `[out_min, out_max]`, with the corresponding components
+    /// of the bounds: `out_min + (self - in_min) * (out_max - out_min) / (in_max - in_min)`.
+    /// The result is not clamped. Components where `in_min == in_max` divide by zero, giving infinities or NaN.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let v = Dvec4::new(15.0, 10.0, 20.0, 25.0);
+    /// let remapped = v.remap_scalar(10.0, 20.0, 0.0, 1.0);
+    /// assert_eq!(remapped, Dvec4::new(0.5, 0.0, 1.0, 1.5));
+    /// ```
+    fn remap(&self, in_min: Self, in_max: Self, out_min: Self, out_max: Self) -> Self {
+        out_min + (*self - in_min) * (out_max - out_min) / (in_max - in_min)
+    }
+
+    /// Same as [`Vector::remap`] with the same bounds for all components.
+    fn remap_scalar(&self, in_min: S, in_max: S, out_min: S, out_max: S) -> Self {
+        self.remap(
+            Self::splat(in_min),
+            Self::splat(in_max),
+            Self::splat(out_min),
+            Self::splat(out_max),
+        )
+    }
+
+    /// Interpolate between two vectors of angles in radians, following the shortest path for each component.
+    /// The difference `rhs - self` is wrapped into `(-π, π]` before interpolating, and so is the result.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// // From 3 to -3, the short way goes through π instead of 0
+    /// let a = Dvec4::splat(3.0);
+    /// let b = Dvec4::splat(-3.0);
+    /// let pi = std::f64::consts::PI;
+    /// assert!(a.lerp_angle(b, 0.25).max_abs_diff(Dvec4::splat(3.0 + (pi - 3.0) / 2.0)) < 1e-12);
+    /// assert!(a.lerp_angle(b, 0.75).max_abs_diff(Dvec4::splat(-3.0 - (pi - 3.0) / 2.0)) < 1e-12);
+    /// ```
+    fn lerp_angle(&self, rhs: Self, t: S) -> Self {
+        let pi = S::from(std::f64::consts::PI).unwrap();
+        let tau = pi + pi;
+        let wrap = |v: Self| v + ((Self::splat(pi) - v) / tau).floor() * tau;
+        wrap(*self + wrap(rhs - *self) * t)
+    }
+
+    /// Sum of all the components.
+    fn sum_reduce(&self) -> S {
+        self.as_slice().iter().fold(S::zero(), |acc, &x| acc + x)
+    }
+
+    /// Product of all the components.
+    fn product_reduce(&self) -> S {
+        self.as_slice().iter().fold(S::one(), |acc, &x| acc * x)
+    }
+
+    /// Compute `1 / x` for each component.
+    fn recip(&self) -> Self {
+        Self::splat(S::one()).div_componentwise(*self)
+    }
+
+    /// Geometric mean of the components: the n-th root of their product.
+    /// The result is NaN if the product is negative, and zero if any component is zero.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::splat(4.0).geometric_mean(), 4.0);
+    /// ```
+    fn geometric_mean(&self) -> S {
+        let n = S::from(self.as_slice().len()).unwrap();
+        self.product_reduce().powf(n.recip())
+    }
+
+    /// Harmonic mean of the components: `n / sum(1 / x)`.
+    /// A zero component makes the sum infinite and the result zero; mixed signs can give any value, including infinity.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::new(1.0, 2.0, 4.0, 4.0).harmonic_mean(), 2.0);
+    /// ```
+    fn harmonic_mean(&self) -> S {
+        let n = S::from(self.as_slice().len()).unwrap();
+        n / self.recip().sum_reduce()
+    }
 }
 
-/// Methods on four-dimensional vectors.
+/// Methods on two-dimensional vectors.
 ///
 /// - `S` is the type of the vector's components.
-pub trait Vec4<S>
+pub trait Vec2<S>
 where
-    Self: VecOps<S>,
-    S: Float,
+    Self: Vector<S>,
+    S: Float + ScalarOps<Self>,
 {
     // --------------- Required methods ---------------
 
     /// Create a new two-dimensional vector.
-    fn new(x: S, y: S, y: S, z: S) -> Self;
+    fn new(x: S, y: S) -> Self;
 
     /// Convert to an array.
     /// Can also use the indexing operator `[]`.
-    fn as_array(&self) -> &[S; 4];
+    fn as_array(&self) -> &[S; 2];
 
     /// Convert to a mutable array.
     /// Can also use the indexing operator`[]`.
-    fn as_mut_array(&mut self) -> &mut [S; 4];
+    fn as_mut_array(&mut self) -> &mut [S; 2];
 
-    /// Add component by component.
-    /// Can also use the `+` operator.
-    fn add_componentwise(&self, rhs: Self) -> Self;
+    /// Negate the components for which `mask` is true, by flipping their sign bit.
+    fn flip_axes(&self, mask: [bool; 2]) -> Self;
 
-    /// Subtract component by component.
-    /// Can also use the `-` operator.
-    fn sub_componentwise(&self, rhs: Self) -> Self;
+    // --------------- Provided methods ---------------
 
-    /// Multiply component by component.
-    /// Can also use the `*` operator.
-    fn mul_componentwise(&self, rhs: Self) -> Self;
+    /// Negate the second component, e.g. to go between screen space and world space.
+    fn flip_y(&self) -> Self {
+        self.flip_axes([false, true])
+    }
 
-    /// Divide component by component.
-    /// Can also use the `/` operator.
-    fn div_componentwise(&self, rhs: Self) -> Self;
+    /// Complex exponential, where the vector `(x, y)` is the complex number `x + iy`.
+    /// The result is `e^x (cos y, sin y)`.
+    ///
+    /// ```
+    /// use mafs::{Vec2, Vector, Dvec2};
+    /// use std::f64::consts::PI;
+    ///
+    /// assert!(Dvec2::new(0.0, PI).complex_exp().max_abs_diff(Dvec2::new(-1.0, 0.0)) < 1e-15);
+    ///
+    /// let z = Dvec2::new(0.5, -2.0);
+    /// assert!(z.complex_exp().complex_ln().max_abs_diff(z) < 1e-15);
+    /// ```
+    fn complex_exp(&self) -> Self {
+        let (sin, cos) = self[1].sin_cos();
+        Self::new(cos, sin) * self[0].exp()
+    }
 
-    /// For each lane, select the smallest component of the two.
-    fn min_componentwise(&self, rhs: Self) -> Self;
+    /// Principal complex logarithm, where the vector `(x, y)` is the complex number `x + iy`.
+    /// The result is `(ln |z|, arg z)`, where the argument is in `(-π, π]`.
+    fn complex_ln(&self) -> Self {
+        Self::new(self.norm().ln(), self[1].atan2(self[0]))
+    }
 
-    /// For each lane, select the largest component of the two.
-    fn max_componentwise(&self, rhs: Self) -> Self;
+    /// Wedge product, i.e. the 2D cross product `x * rhs.y - y * rhs.x`.
+    /// This is the signed area of the parallelogram spanned by the two vectors,
+    /// positive when `rhs` is counterclockwise from `self`.
+    ///
+    /// ```
+    /// use mafs::{Vec2, Dvec2};
+    ///
+    /// assert_eq!(Dvec2::new(1.0, 0.0).wedge(Dvec2::new(0.0, 1.0)), 1.0);
+    /// assert_eq!(Dvec2::new(0.0, 1.0).wedge(Dvec2::new(1.0, 0.0)), -1.0);
+    /// ```
+    fn wedge(&self, rhs: Self) -> S {
+        self[0] * rhs[1] - self[1] * rhs[0]
+    }
 
-    /// Round down all components to an integer value.
-    fn floor(&self) -> Self;
+    /// Binary exponent of each component, see [`Vec4::ilog2`].
+    ///
+    /// ```
+    /// use mafs::{Vec2, Dvec2};
+    ///
+    /// assert_eq!(Dvec2::new(0.75, -1024.0).ilog2(), [-1, 10]);
+    /// ```
+    fn ilog2(&self) -> [i32; 2] {
+        self.as_array().map(exponent)
+    }
+}
 
-    /// Smallest of the four components.
-    fn min_reduce(&self) -> S;
+/// Methods on four-dimensional vectors.
+///
+/// - `S` is the type of the vector's components.
+pub trait Vec4<S>
+where
+    Self: Vector<S>,
+    S: Float,
+{
+    // --------------- Required methods ---------------
 
-    /// Largest of the four components.
-    fn max_reduce(&self) -> S;
+    /// Create a new two-dimensional vector.
+    fn new(x: S, y: S, y: S, z: S) -> Self;
 
-    /// Equality of a vector to another on all components.
-    fn eq_reduce(&self, rhs: Self) -> bool;
+    /// Convert to an array.
+    /// Can also use the indexing operator `[]`.
+    fn as_array(&self) -> &[S; 4];
 
-    /// Dot product.
-    fn dot(&self, rhs: Self) -> S;
+    /// Convert to a mutable array.
+    /// Can also use the indexing operator`[]`.
+    fn as_mut_array(&mut self) -> &mut [S; 4];
 
     /// Cross product.
     /// The fourth component of the operands is ignored and the fourth component of the result will be zero.
     fn cross(&self, rhs: Self) -> Self;
 
-    // --------------- Provided methods ---------------
+    /// Negate the components for which `mask` is true, by flipping their sign bit.
+    fn flip_axes(&self, mask: [bool; 4]) -> Self;
 
-    /// Create a two-dimensional vector with all equal components.
-    fn splat(value: S) -> Self {
-        Self::new(value, value, value, value)
-    }
+    /// Smallest of the first three components, i.e. the fourth component is ignored.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::new(2.0, 3.0, 5.0, 0.0).min_reduce3(), 2.0);
+    /// assert_eq!(Dvec4::new(2.0, 3.0, 5.0, 1e-9).min_reduce3(), 2.0);
+    /// ```
+    fn min_reduce3(&self) -> S;
 
-    /// Norm of this vector.
-    fn norm(&self) -> S {
-        self.dot(*self).sqrt()
+    /// Largest of the first three components, i.e. the fourth component is ignored.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::new(2.0, 3.0, 5.0, 0.0).max_reduce3(), 5.0);
+    /// assert_eq!(Dvec4::new(2.0, 3.0, 5.0, 100.0).max_reduce3(), 5.0);
+    /// ```
+    fn max_reduce3(&self) -> S;
+
+    /// Dot product where the reduction uses fused multiply-adds, so that some of the products are never rounded.
+    /// The result may differ from [`Vector::dot`] by a few ULP, and is usually closer to the exact value.
+    fn dot_fma(&self, rhs: Self) -> S;
+
+    // --------------- Provided methods ---------------
+
+    /// Negate the second component, e.g. to go between screen space and world space.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::new(1.0, 2.0, 3.0, 4.0).flip_y(), Dvec4::new(1.0, -2.0, 3.0, 4.0));
+    /// ```
+    fn flip_y(&self) -> Self {
+        self.flip_axes([false, true, false, false])
     }
 
-    /// Divide by the norm to obain a normalized vector.
-    fn normalize(&self) -> Self {
-        self.div(Self::splat(self.norm()))
+    /// Negate the third component.
+    /// Converting between a right-handed and a left-handed coordinate system is done this way.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::new(1.0, 2.0, 3.0, 4.0).flip_z(), Dvec4::new(1.0, 2.0, -3.0, 4.0));
+    /// ```
+    fn flip_z(&self) -> Self {
+        self.flip_axes([false, false, true, false])
     }
 
     /// Create a point in 3D space, i.e. the fourth component is 1.
@@ -207,6 +755,278 @@ where
     fn direction(x: S, y: S, z: S) -> Self {
         Self::new(x, y, z, S::zero())
     }
+
+    /// Overwrite all the components from an array.
+    fn set_array(&mut self, array: [S; 4]) {
+        *self.as_mut_array() = array;
+    }
+
+    /// Rearrange the components: the component `i` of the result is the component `indices[i]` of `self`.
+    /// The order is only known at runtime, so this goes through memory and is not as fast as a shuffle.
+    /// Panics if an index is not less than 4.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// let v = Dvec4::new(1.0, 2.0, 3.0, 4.0);
+    /// assert_eq!(v.permute_runtime([3, 2, 1, 0]), Dvec4::new(4.0, 3.0, 2.0, 1.0));
+    /// assert_eq!(v.permute_runtime([0, 0, 2, 2]), Dvec4::new(1.0, 1.0, 3.0, 3.0));
+    /// ```
+    fn permute_runtime(&self, indices: [usize; 4]) -> Self {
+        let a = self.as_array();
+        Self::new(a[indices[0]], a[indices[1]], a[indices[2]], a[indices[3]])
+    }
+
+    /// Dot product of the first three components, i.e. the fourth component is ignored.
+    fn dot3(&self, rhs: Self) -> S {
+        self[0] * rhs[0] + self[1] * rhs[1] + self[2] * rhs[2]
+    }
+
+    /// Same as [`Vector::is_normalized`], but for the first three components, i.e. the fourth component is ignored.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// assert!(Dvec4::new(0.0, 0.6, 0.8, 1.0).is_normalized3(1e-12));
+    /// assert!(!Dvec4::new(0.0, 0.6, 0.8, 1.0).is_normalized(1e-12));
+    /// ```
+    fn is_normalized3(&self, eps: S) -> bool {
+        (self.dot3(*self) - S::one()).abs() <= eps
+    }
+
+    /// Remove the component along `normal` so that the result lies in the plane orthogonal to it.
+    /// Both vectors are treated as 3D directions. `normal` must be unit length.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// let velocity = Dvec4::new(1.0, -1.0, 0.0, 0.0);
+    /// let ground = Dvec4::new(0.0, 1.0, 0.0, 0.0);
+    /// assert_eq!(velocity.project_to_plane(ground), Dvec4::new(1.0, 0.0, 0.0, 0.0));
+    /// ```
+    fn project_to_plane(&self, normal: Self) -> Self {
+        *self - normal * self.dot3(normal)
+    }
+
+    /// Spherical angles `(theta, phi)` of the direction given by the x, y and z components. The fourth component
+    /// and the norm are ignored. The pole is +z: `theta` is the angle from +z, in `[0, π]`, and `phi` is the angle
+    /// from +x towards +y around the z axis, in `[-π, π]`.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let x = Dvec4::from_spherical(FRAC_PI_2, 0.0);
+    /// assert!(x.max_abs_diff(Dvec4::direction(1.0, 0.0, 0.0)) < 1e-12);
+    ///
+    /// let v = Dvec4::direction(-0.48, 0.6, 0.64);
+    /// let (theta, phi) = v.to_spherical();
+    /// assert!(Dvec4::from_spherical(theta, phi).max_abs_diff(v) < 1e-12);
+    /// ```
+    fn to_spherical(&self) -> (S, S) {
+        let theta = self[0].hypot(self[1]).atan2(self[2]);
+        let phi = self[1].atan2(self[0]);
+        (theta, phi)
+    }
+
+    /// Unit direction from spherical angles, the inverse of [`Vec4::to_spherical`]. The fourth component is zero.
+    fn from_spherical(theta: S, phi: S) -> Self {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        Self::direction(sin_theta * cos_phi, sin_theta * sin_phi, cos_theta)
+    }
+
+    /// Rotate this direction towards `axis` so that the angle between them is at most `max_angle` radians.
+    /// Directions already inside the cone are returned unchanged, and the norm is preserved.
+    /// Both vectors are treated as 3D directions, the fourth component of a clamped result is zero.
+    /// `axis` must be unit length. If `self` is opposite to `axis`, the rotation plane is chosen arbitrarily.
+    fn clamp_to_cone(&self, axis: Self, max_angle: S) -> Self {
+        let norm = self.dot3(*self).sqrt();
+        let along = self.dot3(axis);
+        let (sin_max, cos_max) = max_angle.sin_cos();
+        if along >= norm * cos_max {
+            return *self;
+        }
+
+        let mut perpendicular = self.project_to_plane(axis);
+        perpendicular[3] = S::zero();
+        let mut perpendicular_norm = perpendicular.dot3(perpendicular).sqrt();
+        if perpendicular_norm <= norm * S::epsilon() {
+            // Antiparallel: any direction orthogonal to the axis will do
+            let (zero, one) = (S::zero(), S::one());
+            let other = if axis[0].abs() < S::from(0.9).unwrap() {
+                Self::direction(one, zero, zero)
+            } else {
+                Self::direction(zero, one, zero)
+            };
+            perpendicular = axis.cross(other);
+            perpendicular_norm = perpendicular.dot3(perpendicular).sqrt();
+        }
+
+        let mut result = (axis * cos_max + perpendicular * (sin_max / perpendicular_norm)) * norm;
+        result[3] = S::zero();
+        result
+    }
+
+    /// Angle in radians between this direction and `rhs`, in `[0, pi]`.
+    /// Both vectors are treated as 3D directions and do not need to be unit length.
+    /// Computed with `atan2` of the norm of the cross product and the dot product, which is accurate
+    /// even for nearly parallel directions, unlike `acos` of the dot product.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    /// use std::f64::consts::FRAC_PI_2;
+    ///
+    /// let x = Dvec4::direction(1.0, 0.0, 0.0);
+    /// let y = Dvec4::direction(0.0, 3.0, 0.0);
+    /// assert_eq!(x.angle_to(y), FRAC_PI_2);
+    /// ```
+    fn angle_to(&self, rhs: Self) -> S {
+        let cross = self.cross(rhs);
+        cross.dot3(cross).sqrt().atan2(self.dot3(rhs))
+    }
+
+    /// Spherical linear interpolation between two unit directions, where `t` is clamped to `[0, 1]`.
+    /// See [`Vec4::slerp_unclamped`].
+    fn slerp(&self, rhs: Self, t: S) -> Self {
+        self.slerp_unclamped(rhs, t.max(S::zero()).min(S::one()))
+    }
+
+    /// Spherical linear interpolation between two unit directions: the result moves along the great circle
+    /// from `self` to `rhs` at a constant angular velocity. Values of `t` outside of `[0, 1]` extrapolate along
+    /// the same great circle, e.g. `t = 2` is as far past `rhs` as `rhs` is from `self`.
+    ///
+    /// Both vectors are treated as 3D directions, the fourth component of the result is zero.
+    /// If the directions are opposite, every great circle through them is the shortest, and one is chosen
+    /// arbitrarily.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let x = Dvec4::direction(1.0, 0.0, 0.0);
+    /// let y = Dvec4::direction(0.0, 1.0, 0.0);
+    /// let diagonal = Dvec4::direction(1.0, 1.0, 0.0).normalize();
+    /// assert!(x.slerp_unclamped(y, 0.5).max_abs_diff(diagonal) < 1e-12);
+    /// assert!(x.slerp_unclamped(y, 2.0).max_abs_diff(-x) < 1e-12);
+    /// assert!(x.slerp(y, 2.0).max_abs_diff(y) < 1e-12);
+    /// ```
+    fn slerp_unclamped(&self, rhs: Self, t: S) -> Self {
+        let along = self.dot3(rhs);
+        let mut perpendicular = rhs - *self * along;
+        perpendicular[3] = S::zero();
+        let mut perpendicular_norm = perpendicular.dot3(perpendicular).sqrt();
+        if perpendicular_norm <= S::epsilon() {
+            if along > S::zero() {
+                // Same direction
+                let mut result = *self;
+                result[3] = S::zero();
+                return result;
+            }
+            // Opposite directions: any direction orthogonal to `self` will do
+            let (zero, one) = (S::zero(), S::one());
+            let other = if self[0].abs() < S::from(0.9).unwrap() {
+                Self::direction(one, zero, zero)
+            } else {
+                Self::direction(zero, one, zero)
+            };
+            perpendicular = self.cross(other);
+            perpendicular_norm = perpendicular.dot3(perpendicular).sqrt();
+        }
+
+        let (sin, cos) = (self.angle_to(rhs) * t).sin_cos();
+        let mut result = *self * cos + perpendicular * (sin / perpendicular_norm);
+        result[3] = S::zero();
+        result
+    }
+
+    /// Clamp this vector componentwise between `min` and `max` in the frame whose axes are the columns of
+    /// `basis`, i.e. inside a box that is oriented like `basis`.
+    /// The vector is expressed in that frame with [`Mat4::mul_vector_transpose`], so `basis` must be orthonormal,
+    /// e.g. a rotation matrix. With the identity, this is the same as [`Vector::clamp`].
+    fn clamp_in_basis<M: Mat4<S, Self>>(&self, basis: M, min: Self, max: Self) -> Self {
+        basis.mul_vector(basis.mul_vector_transpose(*self).clamp(min, max))
+    }
+
+    /// Binary exponent of each component, i.e. `floor(log2(|x|))`, computed exactly from the bit representation
+    /// rather than with a logarithm. Subnormal values are handled exactly too.
+    /// The result is `i32::MIN` for zero, and larger than for any finite value for infinity and NaN.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::new(1.0, 2.0, 4.0, 7.0).ilog2(), [0, 1, 2, 2]);
+    /// assert_eq!(Dvec4::new(0.3, -5.0, 0.0, f64::MIN_POSITIVE / 4.0).ilog2(), [-2, 2, i32::MIN, -1024]);
+    /// ```
+    fn ilog2(&self) -> [i32; 4] {
+        self.as_array().map(exponent)
+    }
+
+    /// Scale the first three components so that they lie on the unit sphere. The fourth component is unchanged,
+    /// so a point remains a point. The result is NaN if the first three components are all zero.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// let p = Dvec4::point(2.0, -3.0, 6.0).project_to_unit_sphere();
+    /// assert!((p.dot3(p) - 1.0).abs() < 1e-15);
+    /// assert_eq!(p[3], 1.0);
+    /// ```
+    fn project_to_unit_sphere(&self) -> Self {
+        let mut result = *self / self.dot3(*self).sqrt();
+        result[3] = self[3];
+        result
+    }
+
+    /// Scale the first three components so that they lie on the surface of the cube `[-1, 1]³`, i.e. the
+    /// largest of their absolute values is 1. The fourth component is unchanged, so a point remains a point.
+    /// The result is NaN if the first three components are all zero.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Vector, Dvec4};
+    ///
+    /// let p = Dvec4::point(2.0, -3.0, 6.0).project_to_unit_cube();
+    /// assert_eq!(p.abs().max_reduce3(), 1.0);
+    /// assert_eq!(p, Dvec4::point(2.0 / 6.0, -3.0 / 6.0, 1.0));
+    /// ```
+    fn project_to_unit_cube(&self) -> Self {
+        let mut result = *self / self.abs().max_reduce3();
+        result[3] = self[3];
+        result
+    }
+
+    /// Signed unit basis vector along the first three components with the largest absolute value.
+    /// The fourth component is ignored and is zero in the result. On a tie, the lowest index wins.
+    ///
+    /// ```
+    /// use mafs::{Vec4, Dvec4};
+    ///
+    /// assert_eq!(Dvec4::new(0.2, -0.9, 0.3, 0.0).dominant_axis(), Dvec4::new(0.0, -1.0, 0.0, 0.0));
+    /// assert_eq!(Dvec4::new(0.5, 0.1, -0.5, 7.0).dominant_axis(), Dvec4::new(1.0, 0.0, 0.0, 0.0));
+    /// ```
+    fn dominant_axis(&self) -> Self {
+        let abs = self.abs();
+        let mut index = 0;
+        for i in 1..3 {
+            if abs[i] > abs[index] {
+                index = i;
+            }
+        }
+        let mut result = Self::splat(S::zero());
+        result[index] = self[index].signum();
+        result
+    }
+}
+
+/// Range of the depth in clip space, after the division by `w`, used by the projection matrices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipSpace {
+    /// OpenGL convention: the near plane maps to -1 and the far plane to 1.
+    NegativeOneToOne,
+    /// Direct3D, Vulkan, Metal and wgpu convention: the near plane maps to 0 and the far plane to 1.
+    ZeroToOne,
+    /// Reversed depth: the near plane maps to 1 and the far plane to 0.
+    /// Combined with a floating point depth buffer, this spreads the precision much more evenly.
+    ReversedZ,
 }
 
 /// Methods on a 4x4 matrices.
@@ -251,6 +1071,16 @@ where
         )
     }
 
+    /// Iterate over the four columns.
+    fn columns(&self) -> impl Iterator<Item = V> {
+        (*self.as_array()).into_iter()
+    }
+
+    /// Iterate over the sixteen components in column-major order.
+    fn elements(&self) -> impl Iterator<Item = S> {
+        self.columns().flat_map(|c| *c.as_array())
+    }
+
     /// Create a new 4x4 matrix from its four rows
     fn from_rows(r0: [S; 4], r1: [S; 4], r2: [S; 4], r3: [S; 4]) -> Self {
         Self::from_columns(
@@ -261,6 +1091,50 @@ where
         )
     }
 
+    /// Create a new 4x4 matrix from its four rows, given as vectors.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let identity = Dmat4::from_row_vectors(
+    ///     Dvec4::new(1.0, 0.0, 0.0, 0.0),
+    ///     Dvec4::new(0.0, 1.0, 0.0, 0.0),
+    ///     Dvec4::new(0.0, 0.0, 1.0, 0.0),
+    ///     Dvec4::new(0.0, 0.0, 0.0, 1.0),
+    /// );
+    /// assert_eq!(identity, Dmat4::identity());
+    ///
+    /// let r0 = Dvec4::new(1.0, 2.0, 3.0, 4.0);
+    /// let r1 = Dvec4::new(5.0, 6.0, 7.0, 8.0);
+    /// let r2 = Dvec4::new(9.0, 10.0, 11.0, 12.0);
+    /// let r3 = Dvec4::new(13.0, 14.0, 15.0, 16.0);
+    /// let m = Dmat4::from_row_vectors(r0, r1, r2, r3);
+    /// assert_eq!(m, Dmat4::from_columns(r0, r1, r2, r3).transpose());
+    /// assert_eq!(m, Dmat4::from_rows(
+    ///     [1.0, 2.0, 3.0, 4.0],
+    ///     [5.0, 6.0, 7.0, 8.0],
+    ///     [9.0, 10.0, 11.0, 12.0],
+    ///     [13.0, 14.0, 15.0, 16.0],
+    /// ));
+    /// ```
+    fn from_row_vectors(r0: V, r1: V, r2: V, r3: V) -> Self {
+        Self::from_columns(r0, r1, r2, r3).transpose()
+    }
+
+    /// Create a new 4x4 matrix from its sixteen components in column-major order.
+    /// Panics if the iterator does not yield exactly sixteen components.
+    fn from_elements(elements: impl IntoIterator<Item = S>) -> Self {
+        let mut columns = [V::splat(S::zero()); 4];
+        let mut elements = elements.into_iter();
+        for c in columns.iter_mut() {
+            for x in c.as_mut_array() {
+                *x = elements.next().expect("expected 16 elements, got fewer");
+            }
+        }
+        assert!(elements.next().is_none(), "expected 16 elements, got more");
+        Self::from_columns(columns[0], columns[1], columns[2], columns[3])
+    }
+
     /// Identity matrix.
     fn identity() -> Self {
         Self::from_columns(
@@ -271,6 +1145,286 @@ where
         )
     }
 
+    /// Translation matrix. The fourth component of `v` is ignored.
+    fn from_translation(v: V) -> Self {
+        let mut m = Self::identity();
+        m[3] = v;
+        m[3][3] = S::one();
+        m
+    }
+
+    /// Rotation of `angle` radians around the x axis, counterclockwise when looking from +x towards the origin.
+    fn from_rotation_x(angle: S) -> Self {
+        let (s, c) = angle.sin_cos();
+        let (zero, one) = (S::zero(), S::one());
+        Self::from_columns(
+            V::new(one, zero, zero, zero),
+            V::new(zero, c, s, zero),
+            V::new(zero, -s, c, zero),
+            V::new(zero, zero, zero, one),
+        )
+    }
+
+    /// Rotation of `angle` radians around the y axis, counterclockwise when looking from +y towards the origin.
+    fn from_rotation_y(angle: S) -> Self {
+        let (s, c) = angle.sin_cos();
+        let (zero, one) = (S::zero(), S::one());
+        Self::from_columns(
+            V::new(c, zero, -s, zero),
+            V::new(zero, one, zero, zero),
+            V::new(s, zero, c, zero),
+            V::new(zero, zero, zero, one),
+        )
+    }
+
+    /// Rotation of `angle` radians around the z axis, counterclockwise when looking from +z towards the origin.
+    fn from_rotation_z(angle: S) -> Self {
+        let (s, c) = angle.sin_cos();
+        let (zero, one) = (S::zero(), S::one());
+        Self::from_columns(
+            V::new(c, s, zero, zero),
+            V::new(-s, c, zero, zero),
+            V::new(zero, zero, one, zero),
+            V::new(zero, zero, zero, one),
+        )
+    }
+
+    /// Scale matrix along the x, y and z axes. The fourth component of `v` is ignored.
+    fn from_scale(v: V) -> Self {
+        let mut m = Self::identity();
+        m[0][0] = v[0];
+        m[1][1] = v[1];
+        m[2][2] = v[2];
+        m
+    }
+
+    /// Outer product of two vectors, i.e. the matrix whose component at row `i` and column `j` is `a[i] * b[j]`.
+    fn outer(a: V, b: V) -> Self {
+        Self::from_columns(a * b[0], a * b[1], a * b[2], a * b[3])
+    }
+
+    /// Householder reflection across the hyperplane orthogonal to `v`, i.e. `identity - 2 * outer(v, v) / v.dot(v)`.
+    /// `v` does not need to be unit length. The result is symmetric and orthogonal, with a determinant of -1.
+    fn householder(v: V) -> Self {
+        Self::identity() - Self::outer(v, v) * ((S::one() + S::one()) / v.dot(v))
+    }
+
+    /// Orthogonal projection onto the line directed by `axis`, i.e. `outer(axis, axis) / axis.dot(axis)`.
+    fn project_onto_axis(axis: V) -> Self {
+        Self::outer(axis, axis) * (S::one() / axis.dot(axis))
+    }
+
+    /// Orthogonal projection onto the plane orthogonal to `normal`, i.e. `identity - outer(normal, normal)`.
+    /// `normal` must be unit length.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let p = Dmat4::project_onto_plane(Dvec4::direction(0.0, 1.0, 0.0));
+    /// assert_eq!(p * Dvec4::direction(1.0, 1.0, 0.0), Dvec4::direction(1.0, 0.0, 0.0));
+    ///
+    /// let p = Dmat4::project_onto_axis(Dvec4::direction(0.0, 2.0, 0.0));
+    /// assert_eq!(p * Dvec4::direction(1.0, 1.0, 0.0), Dvec4::direction(0.0, 1.0, 0.0));
+    /// ```
+    fn project_onto_plane(normal: V) -> Self {
+        Self::identity() - Self::outer(normal, normal)
+    }
+
+    /// Transformation that places an object at `position`, with its local z axis pointing at `camera_pos`.
+    /// The local y axis is as close to `up` as possible.
+    ///
+    /// The result is NaN if the object is at the camera position or if `up` is aligned with the direction
+    /// to the camera.
+    fn billboard(position: V, camera_pos: V, up: V) -> Self {
+        let z = V::direction(
+            camera_pos[0] - position[0],
+            camera_pos[1] - position[1],
+            camera_pos[2] - position[2],
+        )
+        .normalize();
+        let x = up.cross(z).normalize();
+        let y = z.cross(x);
+        Self::from_columns(x, y, z, V::point(position[0], position[1], position[2]))
+    }
+
+    /// Viewport transformation, from normalized device coordinates in `[-1, 1]` to window coordinates
+    /// in `[x, x + width] × [y, y + height]`, with the depth going from `[-1, 1]` to `[near, far]`.
+    ///
+    /// The y axis points up, i.e. `(x, y)` is the bottom-left corner of the viewport.
+    /// For a top-left origin, use `y + height` as `y` and `-height` as `height`.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let viewport = Dmat4::viewport(10.0, 20.0, 800.0, 600.0, 0.0, 1.0);
+    /// assert_eq!(viewport * Dvec4::point(-1.0, -1.0, -1.0), Dvec4::point(10.0, 20.0, 0.0));
+    /// assert_eq!(viewport * Dvec4::point(1.0, 1.0, 1.0), Dvec4::point(810.0, 620.0, 1.0));
+    /// ```
+    fn viewport(x: S, y: S, width: S, height: S, near: S, far: S) -> Self {
+        let zero = S::zero();
+        let half = S::from(0.5).unwrap();
+        Self::from_columns(
+            V::new(width * half, zero, zero, zero),
+            V::new(zero, height * half, zero, zero),
+            V::new(zero, zero, (far - near) * half, zero),
+            V::point(x + width * half, y + height * half, (far + near) * half),
+        )
+    }
+
+    /// Right-handed perspective projection: the camera looks towards -z with +y up.
+    /// `fov_y` is the vertical field of view in radians and `aspect` is the ratio width / height.
+    /// The depth of the near and far planes in clip space is given by `clip`.
+    ///
+    /// ```
+    /// use mafs::{ClipSpace, Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let m = Dmat4::perspective(1.0, 1.5, 2.0, 10.0, ClipSpace::ZeroToOne);
+    /// let near = m * Dvec4::point(0.0, 0.0, -2.0);
+    /// let far = m * Dvec4::point(0.0, 0.0, -10.0);
+    /// assert_eq!(near[2] / near[3], 0.0);
+    /// assert_eq!(far[2] / far[3], 1.0);
+    /// ```
+    fn perspective(fov_y: S, aspect: S, near: S, far: S, clip: ClipSpace) -> Self {
+        let top = near * (fov_y / (S::one() + S::one())).tan();
+        let right = top * aspect;
+        Self::frustum(-right, right, -top, top, near, far, clip)
+    }
+
+    /// Right-handed orthographic projection of the box `[left, right] × [bottom, top] × [-far, -near]`:
+    /// the camera looks towards -z with +y up.
+    /// The depth of the near and far planes in clip space is given by `clip`.
+    fn orthographic(
+        left: S,
+        right: S,
+        bottom: S,
+        top: S,
+        near: S,
+        far: S,
+        clip: ClipSpace,
+    ) -> Self {
+        let (zero, one, two) = (S::zero(), S::one(), S::one() + S::one());
+        let depth = far - near;
+        let (m22, m23) = match clip {
+            ClipSpace::NegativeOneToOne => (-two / depth, -(far + near) / depth),
+            ClipSpace::ZeroToOne => (-one / depth, -near / depth),
+            ClipSpace::ReversedZ => (one / depth, far / depth),
+        };
+        Self::from_rows(
+            [
+                two / (right - left),
+                zero,
+                zero,
+                -(right + left) / (right - left),
+            ],
+            [
+                zero,
+                two / (top - bottom),
+                zero,
+                -(top + bottom) / (top - bottom),
+            ],
+            [zero, zero, m22, m23],
+            [zero, zero, zero, one],
+        )
+    }
+
+    /// Right-handed perspective projection of the frustum whose near plane is the rectangle
+    /// `[left, right] × [bottom, top]` at depth `-near`, like `glFrustum`.
+    /// The frustum may be asymmetric, e.g. for stereo or off-axis rendering. [`Mat4::perspective`] is the
+    /// symmetric case, where `top = -bottom = near * tan(fov_y / 2)` and `right = -left = top * aspect`.
+    /// The depth of the near and far planes in clip space is given by `clip`.
+    fn frustum(left: S, right: S, bottom: S, top: S, near: S, far: S, clip: ClipSpace) -> Self {
+        let (zero, one, two) = (S::zero(), S::one(), S::one() + S::one());
+        let (m22, m23) = match clip {
+            ClipSpace::NegativeOneToOne => {
+                ((far + near) / (near - far), two * far * near / (near - far))
+            }
+            ClipSpace::ZeroToOne => (far / (near - far), far * near / (near - far)),
+            ClipSpace::ReversedZ => (near / (far - near), far * near / (far - near)),
+        };
+        Self::from_rows(
+            [
+                two * near / (right - left),
+                zero,
+                (right + left) / (right - left),
+                zero,
+            ],
+            [
+                zero,
+                two * near / (top - bottom),
+                (top + bottom) / (top - bottom),
+                zero,
+            ],
+            [zero, zero, m22, m23],
+            [zero, zero, -one, zero],
+        )
+    }
+
+    /// Left-handed variant of [`Mat4::perspective`]: the camera looks towards +z with +y up,
+    /// as is customary with Direct3D. The only difference is the sign of the third column.
+    fn perspective_lh(fov_y: S, aspect: S, near: S, far: S, clip: ClipSpace) -> Self {
+        let mut m = Self::perspective(fov_y, aspect, near, far, clip);
+        m[2] = -m[2];
+        m
+    }
+
+    /// Left-handed variant of [`Mat4::orthographic`]: the box is `[left, right] × [bottom, top] × [near, far]`
+    /// and the camera looks towards +z with +y up. The only difference is the sign of the third column.
+    fn orthographic_lh(
+        left: S,
+        right: S,
+        bottom: S,
+        top: S,
+        near: S,
+        far: S,
+        clip: ClipSpace,
+    ) -> Self {
+        let mut m = Self::orthographic(left, right, bottom, top, near, far, clip);
+        m[2] = -m[2];
+        m
+    }
+
+    /// Right-handed view matrix, from world coordinates to the coordinates of a camera placed at `eye`
+    /// and looking at `target`, in which the camera looks towards -z with +y up.
+    /// `eye` and `target` are points and `up` is a direction, which must not be parallel to the view direction.
+    /// Use together with [`Mat4::perspective`] or [`Mat4::orthographic`].
+    fn look_at(eye: V, target: V, up: V) -> Self {
+        let f = (target - eye).normalize();
+        let s = f.cross(up).normalize();
+        let u = s.cross(f);
+        let (zero, one) = (S::zero(), S::one());
+        Self::from_row_vectors(
+            V::new(s[0], s[1], s[2], -s.dot3(eye)),
+            V::new(u[0], u[1], u[2], -u.dot3(eye)),
+            V::new(-f[0], -f[1], -f[2], f.dot3(eye)),
+            V::new(zero, zero, zero, one),
+        )
+    }
+
+    /// Left-handed variant of [`Mat4::look_at`], in which the camera looks towards +z with +y up.
+    /// Use together with [`Mat4::perspective_lh`] or [`Mat4::orthographic_lh`] when the world coordinates
+    /// are left-handed, e.g. when working with Direct3D conventions.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let origin = Dvec4::point(0.0, 0.0, 0.0);
+    /// let forward = Dvec4::point(0.0, 0.0, 1.0);
+    /// let up = Dvec4::direction(0.0, 1.0, 0.0);
+    /// assert_eq!(Dmat4::look_at_lh(origin, forward, up), Dmat4::identity());
+    /// ```
+    fn look_at_lh(eye: V, target: V, up: V) -> Self {
+        let f = (target - eye).normalize();
+        let s = up.cross(f).normalize();
+        let u = f.cross(s);
+        let (zero, one) = (S::zero(), S::one());
+        Self::from_row_vectors(
+            V::new(s[0], s[1], s[2], -s.dot3(eye)),
+            V::new(u[0], u[1], u[2], -u.dot3(eye)),
+            V::new(f[0], f[1], f[2], -f.dot3(eye)),
+            V::new(zero, zero, zero, one),
+        )
+    }
+
     /// Add component by component.
     /// Can also use the `+` operator.
     fn add_componentwise(&self, rhs: Self) -> Self {
@@ -293,6 +1447,53 @@ where
         )
     }
 
+    /// Multiply all components by a scalar.
+    /// Can also use the `*` operator.
+    fn mul_scalar(&self, rhs: S) -> Self {
+        Self::from_columns(self[0] * rhs, self[1] * rhs, self[2] * rhs, self[3] * rhs)
+    }
+
+    /// Multiply this matrix with a point: the fourth component of `v` is replaced by 1 whatever it was.
+    /// The result is not divided by its fourth component.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let translation = Dmat4::from_columns(
+    ///     Dvec4::new(1.0, 0.0, 0.0, 0.0),
+    ///     Dvec4::new(0.0, 1.0, 0.0, 0.0),
+    ///     Dvec4::new(0.0, 0.0, 1.0, 0.0),
+    ///     Dvec4::new(5.0, 6.0, 7.0, 1.0),
+    /// );
+    /// let v = Dvec4::new(1.0, 2.0, 3.0, 42.0);
+    /// assert_eq!(translation.mul_point3(v), Dvec4::new(6.0, 8.0, 10.0, 1.0));
+    /// assert_eq!(translation.mul_direction3(v), Dvec4::new(1.0, 2.0, 3.0, 0.0));
+    /// ```
+    fn mul_point3(&self, v: V) -> V {
+        self.mul_vector(V::point(v[0], v[1], v[2]))
+    }
+
+    /// Multiply this matrix with a direction: the fourth component of `v` is replaced by 0 whatever it was,
+    /// so the translation is not applied.
+    fn mul_direction3(&self, v: V) -> V {
+        self.mul_vector(V::direction(v[0], v[1], v[2]))
+    }
+
+    /// Transform the axis-aligned box whose corners are the points `min` and `max`,
+    /// and return the corners of the smallest axis-aligned box that encloses the result.
+    ///
+    /// This matrix must be an affine transformation. Instead of transforming the eight corners, the center is
+    /// transformed and the new half-extents are obtained with the absolute values of the matrix (Arvo's method).
+    fn transform_aabb(&self, min: V, max: V) -> (V, V) {
+        let half = S::from(0.5).unwrap();
+        let center = (min + max) * half;
+        let extent = (max - min) * half;
+        let center = self.mul_point3(center);
+        let extent =
+            self[0].abs() * extent[0] + self[1].abs() * extent[1] + self[2].abs() * extent[2];
+        (center - extent, center + extent)
+    }
+
     /// Multiply this matrix with another matrix.
     /// Can also use the `*` operator.
     fn mul_matrix(&self, rhs: Self) -> Self {
@@ -304,6 +1505,534 @@ where
         )
     }
 
+    /// Transpose of the product, i.e. `(self * rhs).transpose()`, which is also `rhs.transpose() * self.transpose()`.
+    ///
+    /// The transposition is fused into the product: the `j`-th column of `self * rhs` is `self * rhs[j]`,
+    /// so it is stored directly as the `j`-th row of the result.
+    fn mul_transposed(&self, rhs: Self) -> Self {
+        Self::from_row_vectors(
+            self.mul_vector(rhs[0]),
+            self.mul_vector(rhs[1]),
+            self.mul_vector(rhs[2]),
+            self.mul_vector(rhs[3]),
+        )
+    }
+
+    /// Same as [`Mat4::mul_matrix`], but assumes that `self` has the zero pattern of a projection matrix
+    /// built by [`Mat4::perspective`] or [`Mat4::orthographic`] (including their left-handed variants):
+    /// the first column is `(p00, 0, 0, 0)` and the second column is `(0, p11, 0, 0)`.
+    /// The other entries of these two columns are ignored, the last two columns can be anything.
+    /// This saves one fused multiply-add per column of the result.
+    fn mul_projection(&self, view: Self) -> Self {
+        let zero = S::zero();
+        let diagonal = V::new(self[0][0], self[1][1], zero, zero);
+        let column = |v: V| v * diagonal + self[2] * v[2] + self[3] * v[3];
+        Self::from_columns(
+            column(view[0]),
+            column(view[1]),
+            column(view[2]),
+            column(view[3]),
+        )
+    }
+
+    /// Transpose this matrix in place. Uses the same shuffles as [`Mat4::transpose`].
+    fn transpose_in_place(&mut self) {
+        *self = self.transpose();
+    }
+
+    /// Post-multiply by a translation matrix: `self * from_translation(v)`.
+    ///
+    /// The builders [`Mat4::translate`], [`Mat4::rotate_x`], [`Mat4::rotate_y`], [`Mat4::rotate_z`] and
+    /// [`Mat4::scale`] all post-multiply. In a chain like `identity().translate(t).rotate_z(a)`, the rotation is
+    /// applied to the object first, then the translation, as if each step moved the local coordinate frame.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Vector, Dvec4};
+    ///
+    /// let t = Dvec4::direction(1.0, 2.0, 3.0);
+    /// let m = Dmat4::identity().translate(t).rotate_z(0.5).scale(Dvec4::splat(2.0));
+    /// let expected = Dmat4::from_translation(t) * Dmat4::from_rotation_z(0.5) * Dmat4::from_scale(Dvec4::splat(2.0));
+    /// assert_eq!(m, expected);
+    /// ```
+    fn translate(&self, v: V) -> Self {
+        *self * Self::from_translation(v)
+    }
+
+    /// Post-multiply by a rotation around the x axis: `self * from_rotation_x(angle)`. See [`Mat4::translate`].
+    fn rotate_x(&self, angle: S) -> Self {
+        *self * Self::from_rotation_x(angle)
+    }
+
+    /// Post-multiply by a rotation around the y axis: `self * from_rotation_y(angle)`. See [`Mat4::translate`].
+    fn rotate_y(&self, angle: S) -> Self {
+        *self * Self::from_rotation_y(angle)
+    }
+
+    /// Post-multiply by a rotation around the z axis: `self * from_rotation_z(angle)`. See [`Mat4::translate`].
+    fn rotate_z(&self, angle: S) -> Self {
+        *self * Self::from_rotation_z(angle)
+    }
+
+    /// Post-multiply by a scale matrix: `self * from_scale(v)`. See [`Mat4::translate`].
+    fn scale(&self, v: V) -> Self {
+        *self * Self::from_scale(v)
+    }
+
+    /// Multiply the transpose of this matrix with another matrix, i.e. `self.transpose() * rhs`.
+    /// Each component of the result is the dot product of a column of `self` and a column of `rhs`.
+    fn transpose_mul(&self, rhs: Self) -> Self {
+        let column = |c: V| {
+            V::new(
+                self[0].dot(c),
+                self[1].dot(c),
+                self[2].dot(c),
+                self[3].dot(c),
+            )
+        };
+        Self::from_columns(
+            column(rhs[0]),
+            column(rhs[1]),
+            column(rhs[2]),
+            column(rhs[3]),
+        )
+    }
+
+    /// Multiply the transpose of this matrix with a vector, i.e. `self.transpose() * v`.
+    /// Each component of the result is the dot product of a column of `self` and `v`.
+    /// For an orthonormal matrix, this applies the inverse transformation.
+    fn mul_vector_transpose(&self, v: V) -> V {
+        V::new(
+            self[0].dot(v),
+            self[1].dot(v),
+            self[2].dot(v),
+            self[3].dot(v),
+        )
+    }
+
+    /// Gram matrix, i.e. `self.transpose() * self`. The result is always symmetric.
+    fn gram(&self) -> Self {
+        self.transpose_mul(*self)
+    }
+
+    /// Swap the columns `i` and `j`. Panics if `i` or `j` is not less than 4.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let m = Dmat4::from_columns(
+    ///     Dvec4::new(1.0, 2.0, 3.0, 4.0),
+    ///     Dvec4::new(5.0, 6.0, 7.0, 8.0),
+    ///     Dvec4::new(9.0, 10.0, 11.0, 12.0),
+    ///     Dvec4::new(13.0, 14.0, 15.0, 16.0),
+    /// );
+    /// let mut swapped = m;
+    /// swapped.swap_columns(0, 3);
+    /// assert_eq!(swapped, Dmat4::from_columns(
+    ///     Dvec4::new(13.0, 14.0, 15.0, 16.0),
+    ///     Dvec4::new(5.0, 6.0, 7.0, 8.0),
+    ///     Dvec4::new(9.0, 10.0, 11.0, 12.0),
+    ///     Dvec4::new(1.0, 2.0, 3.0, 4.0),
+    /// ));
+    /// swapped.swap_columns(0, 3);
+    /// assert_eq!(swapped, m);
+    /// ```
+    fn swap_columns(&mut self, i: usize, j: usize) {
+        self.as_mut_array().swap(i, j);
+    }
+
+    /// Swap the rows `i` and `j`. Panics if `i` or `j` is not less than 4.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let m = Dmat4::from_columns(
+    ///     Dvec4::new(1.0, 2.0, 3.0, 4.0),
+    ///     Dvec4::new(5.0, 6.0, 7.0, 8.0),
+    ///     Dvec4::new(9.0, 10.0, 11.0, 12.0),
+    ///     Dvec4::new(13.0, 14.0, 15.0, 16.0),
+    /// );
+    /// let mut swapped = m;
+    /// swapped.swap_rows(0, 3);
+    /// assert_eq!(swapped, Dmat4::from_columns(
+    ///     Dvec4::new(4.0, 2.0, 3.0, 1.0),
+    ///     Dvec4::new(8.0, 6.0, 7.0, 5.0),
+    ///     Dvec4::new(12.0, 10.0, 11.0, 9.0),
+    ///     Dvec4::new(16.0, 14.0, 15.0, 13.0),
+    /// ));
+    /// swapped.swap_rows(0, 3);
+    /// assert_eq!(swapped, m);
+    /// ```
+    fn swap_rows(&mut self, i: usize, j: usize) {
+        for column in self.as_mut_array() {
+            column.as_mut_array().swap(i, j);
+        }
+    }
+
+    /// Overwrite the upper-left 3x3 block, e.g. to change the orientation of a transform but not its position.
+    /// Only the first three components of the axes are used. The fourth row and the fourth column are untouched.
+    /// The axes are copied as they are, without orthonormalization.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let mut m = Dmat4::from_translation(Dvec4::new(1.0, 2.0, 3.0, 0.0)).rotate_x(0.5);
+    /// m.set_basis(
+    ///     Dvec4::new(0.0, 1.0, 0.0, 9.0),
+    ///     Dvec4::new(-1.0, 0.0, 0.0, 9.0),
+    ///     Dvec4::new(0.0, 0.0, 1.0, 9.0),
+    /// );
+    /// assert_eq!(m * Dvec4::point(1.0, 0.0, 0.0), Dvec4::point(1.0, 3.0, 3.0));
+    /// assert_eq!(m[0], Dvec4::new(0.0, 1.0, 0.0, 0.0));
+    /// assert_eq!(m[3], Dvec4::new(1.0, 2.0, 3.0, 1.0));
+    /// ```
+    fn set_basis(&mut self, x_axis: V, y_axis: V, z_axis: V) {
+        for (column, axis) in self.as_mut_array().iter_mut().zip([x_axis, y_axis, z_axis]) {
+            let w = column[3];
+            *column = axis;
+            column[3] = w;
+        }
+    }
+
+    /// Frobenius norm, i.e. the square root of the sum of the squares of all the components.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// assert_eq!(Dmat4::identity().frobenius_norm(), 2.0);
+    /// assert_eq!(Dmat4::splat(-1.0).frobenius_norm(), 4.0);
+    ///
+    /// let m = Dmat4::from_columns(
+    ///     Dvec4::new(1.0, 2.0, 3.0, 4.0),
+    ///     Dvec4::new(5.0, 6.0, 7.0, 8.0),
+    ///     Dvec4::new(9.0, 10.0, 11.0, 12.0),
+    ///     Dvec4::new(13.0, 14.0, 15.0, 16.0),
+    /// );
+    /// assert_eq!(m.frobenius_norm_squared(), 1496.0);
+    /// assert_eq!(m.frobenius_norm(), 1496.0f64.sqrt());
+    /// ```
+    fn frobenius_norm(&self) -> S {
+        self.frobenius_norm_squared().sqrt()
+    }
+
+    /// Squared Frobenius norm. Cheaper than [`Mat4::frobenius_norm`] when comparing magnitudes.
+    fn frobenius_norm_squared(&self) -> S {
+        self[0].norm_squared()
+            + self[1].norm_squared()
+            + self[2].norm_squared()
+            + self[3].norm_squared()
+    }
+
+    /// Compare two matrices with a tolerance.
+    /// Returns true if all the components differ by at most `eps`.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let m = Dmat4::from_columns(
+    ///     Dvec4::new( 0.6666666666666666,  0.6666666666666666, -0.3333333333333333, 0.0),
+    ///     Dvec4::new(-0.3333333333333333,  0.6666666666666666,  0.6666666666666666, 0.0),
+    ///     Dvec4::new( 0.6666666666666666, -0.3333333333333333,  0.6666666666666666, 0.0),
+    ///     Dvec4::new(               -4.0,                 5.0,                 6.0, 1.0),
+    /// );
+    /// assert!((m * m.inverse_se3()).abs_diff_eq(Dmat4::identity(), 1e-12));
+    /// assert!(!m.abs_diff_eq(Dmat4::identity(), 1e-12));
+    /// ```
+    fn abs_diff_eq(&self, rhs: Self, eps: S) -> bool {
+        let diff = self[0]
+            .max_abs_diff(rhs[0])
+            .max(self[1].max_abs_diff(rhs[1]))
+            .max(self[2].max_abs_diff(rhs[2]))
+            .max(self[3].max_abs_diff(rhs[3]));
+        diff <= eps
+    }
+
+    /// Check that this matrix is equal to its transpose, up to `eps`.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let symmetric = Dmat4::from_rows(
+    ///     [1.0, 2.0, 3.0, 0.0],
+    ///     [2.0, 4.0, 5.0, 0.0],
+    ///     [3.0, 5.0, 6.0, 0.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// );
+    /// assert!(symmetric.is_symmetric(1e-12));
+    ///
+    /// let asymmetric = Dmat4::from_rows(
+    ///     [1.0, 2.0, 3.0, 0.0],
+    ///     [0.0, 4.0, 5.0, 0.0],
+    ///     [3.0, 5.0, 6.0, 0.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// );
+    /// assert!(!asymmetric.is_symmetric(1e-12));
+    /// assert!(asymmetric.symmetric_part().is_symmetric(1e-12));
+    /// ```
+    fn is_symmetric(&self, eps: S) -> bool {
+        self.abs_diff_eq(self.transpose(), eps)
+    }
+
+    /// Eigenvalues and eigenvectors of the upper-left 3x3 block, which must be symmetric
+    /// (e.g. a covariance matrix). Only its upper triangle is read.
+    ///
+    /// Returns the eigenvalues as the first three components of a vector (the fourth is zero), and a rotation matrix
+    /// whose first three columns are the corresponding unit eigenvectors. The eigenvalues are not sorted.
+    ///
+    /// Uses the cyclic Jacobi method, which converges quadratically. It stops when the off-diagonal part is
+    /// negligible, or after 16 sweeps at most, which is more than enough in practice.
+    fn symmetric_eigen_3x3(&self) -> (V, Self) {
+        let (zero, one) = (S::zero(), S::one());
+        // Row-major copies of the block and of the eigenvectors
+        let mut a = [[zero; 3]; 3];
+        let mut v = [[zero; 3]; 3];
+        for r in 0..3 {
+            for c in 0..3 {
+                a[r][c] = self[c.max(r)][c.min(r)];
+            }
+            v[r][r] = one;
+        }
+        let tolerance =
+            a.iter().flatten().fold(zero, |acc, &x| acc + x * x) * S::epsilon() * S::epsilon();
+
+        for _ in 0..16 {
+            let off_diagonal = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+            if off_diagonal <= tolerance {
+                break;
+            }
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                if a[p][q] == zero {
+                    continue;
+                }
+                // Rotation in the (p, q) plane that cancels a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (a[p][q] + a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + one).sqrt());
+                let c = one / (t * t + one).sqrt();
+                let s = t * c;
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    let (xp, xq) = (row[p], row[q]);
+                    row[p] = c * xp - s * xq;
+                    row[q] = s * xp + c * xq;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                for k in 0..3 {
+                    a[p][k] = c * row_p[k] - s * row_q[k];
+                    a[q][k] = s * row_p[k] + c * row_q[k];
+                }
+            }
+        }
+
+        let column = |c: usize| V::new(v[0][c], v[1][c], v[2][c], zero);
+        (
+            V::new(a[0][0], a[1][1], a[2][2], zero),
+            Self::from_columns(
+                column(0),
+                column(1),
+                column(2),
+                V::new(zero, zero, zero, one),
+            ),
+        )
+    }
+
+    /// Estimate the unit eigenvector of the upper-left 3x3 block associated with the eigenvalue of largest magnitude,
+    /// by power iteration: a starting vector is repeatedly multiplied by the matrix and normalized.
+    /// The fourth component of the result is zero, and its sign is arbitrary.
+    ///
+    /// The error shrinks by a factor `|λ2 / λ1|` at each iteration, where `λ1` and `λ2` are the two eigenvalues
+    /// of largest magnitude, so the convergence is slow or does not happen at all when they are close.
+    /// See [`Mat4::symmetric_eigen_3x3`] for a full decomposition.
+    fn dominant_eigenvector(&self, iterations: usize) -> V {
+        let (one, half, quarter) = (S::one(), S::from(0.5).unwrap(), S::from(0.25).unwrap());
+        // Arbitrary start, unlikely to be orthogonal to the dominant eigenvector
+        let mut v = V::direction(one, half, quarter).normalize();
+        for _ in 0..iterations {
+            let w = self.mul_direction3(v);
+            v = V::direction(w[0], w[1], w[2]).normalize_or(v);
+        }
+        v
+    }
+
+    /// Nearest symmetric matrix, i.e. `(self + self.transpose()) / 2`.
+    fn symmetric_part(&self) -> Self {
+        (*self + self.transpose()) * S::from(0.5).unwrap()
+    }
+
+    /// Trace of the upper-left 3x3 block, i.e. the sum of its diagonal.
+    fn trace3(&self) -> S {
+        self[0][0] + self[1][1] + self[2][2]
+    }
+
+    /// Angle in radians, in `[0, π]`, of the rotation in the upper-left 3x3 block, which must be a proper rotation.
+    /// The axis is not considered. Rounding errors that would put the cosine outside of `[-1, 1]` are clamped.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4};
+    ///
+    /// assert!((Dmat4::from_rotation_z(0.7).rotation_angle() - 0.7).abs() < 1e-12);
+    /// assert_eq!(Dmat4::identity().rotation_angle(), 0.0);
+    /// ```
+    fn rotation_angle(&self) -> S {
+        let half = S::from(0.5).unwrap();
+        let cos = (self.trace3() - S::one()) * half;
+        cos.max(-S::one()).min(S::one()).acos()
+    }
+
+    /// Determinant of the upper-left 3x3 block.
+    fn determinant3(&self) -> S {
+        self[0].dot3(self[1].cross(self[2]))
+    }
+
+    /// Determinant of the matrix, by expansion along the 2x2 minors of the first two columns.
+    fn determinant(&self) -> S {
+        let (a, b, c, d) = (self[0], self[1], self[2], self[3]);
+        let s0 = a[0] * b[1] - b[0] * a[1];
+        let s1 = a[0] * b[2] - b[0] * a[2];
+        let s2 = a[0] * b[3] - b[0] * a[3];
+        let s3 = a[1] * b[2] - b[1] * a[2];
+        let s4 = a[1] * b[3] - b[1] * a[3];
+        let s5 = a[2] * b[3] - b[2] * a[3];
+        let c5 = c[2] * d[3] - d[2] * c[3];
+        let c4 = c[1] * d[3] - d[1] * c[3];
+        let c3 = c[1] * d[2] - d[1] * c[2];
+        let c2 = c[0] * d[3] - d[0] * c[3];
+        let c1 = c[0] * d[2] - d[0] * c[2];
+        let c0 = c[0] * d[1] - d[0] * c[1];
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+
+    /// Cofactor of the element at `row` and `col`: the determinant of the 3x3 matrix obtained by removing
+    /// that row and that column, multiplied by `(-1)^(row + col)`.
+    /// Panics if `row` or `col` is not less than 4.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4};
+    ///
+    /// let identity = Dmat4::identity();
+    /// for row in 0..4 {
+    ///     for col in 0..4 {
+    ///         assert_eq!(identity.cofactor(row, col), if row == col { 1.0 } else { 0.0 });
+    ///     }
+    /// }
+    ///
+    /// let m = Dmat4::from_rows(
+    ///     [2.0, 1.0, 0.0, 3.0],
+    ///     [1.0, 3.0, 1.0, 0.0],
+    ///     [0.0, 1.0, 4.0, 1.0],
+    ///     [5.0, 0.0, 1.0, 5.0],
+    /// );
+    /// assert_eq!(m.cofactor(0, 0), 52.0);
+    /// assert_eq!(m.cofactor(0, 1), -24.0);
+    ///
+    /// // Laplace expansion along the first row
+    /// let expansion: f64 = (0..4).map(|col| m[col][0] * m.cofactor(0, col)).sum();
+    /// assert_eq!(expansion, m.determinant());
+    /// ```
+    fn cofactor(&self, row: usize, col: usize) -> S {
+        assert!(row < 4 && col < 4, "index out of bounds");
+        let mut minor = [V::splat(S::zero()); 3];
+        let columns = (0..4).filter(|&j| j != col);
+        for (m, j) in minor.iter_mut().zip(columns) {
+            let rows = (0..4).filter(|&i| i != row);
+            for (k, i) in rows.enumerate() {
+                m[k] = self[j][i];
+            }
+        }
+        let determinant = minor[0].dot3(minor[1].cross(minor[2]));
+        if (row + col) % 2 == 0 {
+            determinant
+        } else {
+            -determinant
+        }
+    }
+
+    /// Check that the absolute value of the determinant is above `eps`.
+    /// This is cheaper than [`Mat4::inverse`], but says nothing about the conditioning: a matrix can pass this
+    /// check and still be too ill-conditioned to be inverted accurately. The determinant also scales with the
+    /// fourth power of the matrix, so `eps` must be chosen according to the magnitude of the elements.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// assert!(Dmat4::identity().is_invertible(1e-12));
+    ///
+    /// let mut m = Dmat4::identity();
+    /// m[1] = m[0];
+    /// assert!(!m.is_invertible(1e-12));
+    /// ```
+    fn is_invertible(&self, eps: S) -> bool {
+        self.determinant().abs() > eps
+    }
+
+    /// Check that the linear part of this transformation preserves the orientation,
+    /// i.e. the determinant of the upper-left 3x3 block is positive. The translation is not considered.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// assert!(Dmat4::identity().is_right_handed());
+    ///
+    /// let mut m = Dmat4::identity();
+    /// m[1] = -m[1];
+    /// assert!(m.flips_orientation());
+    ///
+    /// m[1] = m[0];
+    /// assert!(!m.is_right_handed() && !m.flips_orientation());
+    /// ```
+    fn is_right_handed(&self) -> bool {
+        self.determinant3() > S::zero()
+    }
+
+    /// Check that the linear part of this transformation changes the winding order of triangles,
+    /// i.e. the determinant of the upper-left 3x3 block is negative. The translation is not considered.
+    /// A degenerate block, whose determinant is zero or NaN, neither preserves nor flips the orientation.
+    fn flips_orientation(&self) -> bool {
+        self.determinant3() < S::zero()
+    }
+
+    /// Nearest rotation matrix to the upper-left 3x3 block, in the sense of the Frobenius norm, i.e. the orthogonal
+    /// factor of its polar decomposition. Unlike Gram-Schmidt, no column is favored over the others.
+    /// The fourth column is kept and the fourth row of the first three columns is set to zero.
+    ///
+    /// Computed with the Newton iteration `R = (R + R^-T) / 2`, which converges quadratically. It stops when an
+    /// iteration changes the matrix by less than the machine epsilon, or after 16 iterations at most.
+    /// The block must have a positive determinant.
+    fn closest_rotation(&self) -> Self {
+        let column = |v: V| V::direction(v[0], v[1], v[2]);
+        let (mut a, mut b, mut c) = (column(self[0]), column(self[1]), column(self[2]));
+        let half = S::from(0.5).unwrap();
+        for _ in 0..16 {
+            // The columns of the inverse transpose are the cross products of the columns, divided by the determinant
+            let inv_det = S::one() / a.dot3(b.cross(c));
+            let next_a = (a + b.cross(c) * inv_det) * half;
+            let next_b = (b + c.cross(a) * inv_det) * half;
+            let next_c = (c + a.cross(b) * inv_det) * half;
+            let change = (next_a - a)
+                .abs()
+                .max_componentwise((next_b - b).abs())
+                .max_componentwise((next_c - c).abs());
+            (a, b, c) = (next_a, next_b, next_c);
+            if change.max_reduce() <= S::epsilon() {
+                break;
+            }
+        }
+        Self::from_columns(a, b, c, self[3])
+    }
+
+    /// Scale factors of a transformation matrix, i.e. the norms of the first three columns.
+    /// The fourth component of the result is zero.
+    ///
+    /// If the transformation contains a reflection (the determinant of the upper-left 3x3 block is negative),
+    /// only the product of the signs of the scale factors can be recovered. By convention, the first one is negated.
+    fn extract_scale(&self) -> V {
+        let sx = self[0].dot3(self[0]).sqrt();
+        let sy = self[1].dot3(self[1]).sqrt();
+        let sz = self[2].dot3(self[2]).sqrt();
+        if self.determinant3() < S::zero() {
+            V::new(-sx, sy, sz, S::zero())
+        } else {
+            V::new(sx, sy, sz, S::zero())
+        }
+    }
+
     /// Assume that this matrix is a rotation+translation matrix and computes its inverse.
     /// If this matrix is not a rotation+translation, the result will be nonsense.
     fn inverse_se3(&self) -> Self {
@@ -315,4 +2044,316 @@ where
         m[3][3] = S::one();
         m
     }
+
+    /// Check that this matrix is a rotation+translation matrix, up to `eps`.
+    /// The upper-left 3x3 block must be orthonormal and right-handed, and the bottom row must be `[0, 0, 0, 1]`.
+    fn is_se3(&self, eps: S) -> bool {
+        let (one, zero) = (S::one(), S::zero());
+        let (x, y, z) = (self[0], self[1], self[2]);
+        let bottom_row = V::new(x[3], y[3], z[3], self[3][3]);
+        bottom_row.max_abs_diff(V::new(zero, zero, zero, one)) <= eps
+            && (x.dot3(x) - one).abs() <= eps
+            && (y.dot3(y) - one).abs() <= eps
+            && (z.dot3(z) - one).abs() <= eps
+            && x.dot3(y).abs() <= eps
+            && y.dot3(z).abs() <= eps
+            && z.dot3(x).abs() <= eps
+            && x.cross(y).dot3(z) > zero
+    }
+
+    /// Same as [`Mat4::inverse_se3`], but first checks that this matrix is a rotation+translation matrix
+    /// with [`Mat4::is_se3`]. Returns `None` if it is not.
+    /// The check is not free, use `inverse_se3` when the input is known to be valid.
+    fn try_inverse_se3(&self, eps: S) -> Option<Self> {
+        self.is_se3(eps).then(|| self.inverse_se3())
+    }
+
+    /// Solve the linear system `self * x = b` by Gaussian elimination with partial pivoting.
+    /// Returns `None` if the matrix is singular, i.e. a pivot is too small relative to the largest element.
+    ///
+    /// This is cheaper and more accurate than `self.inverse().map(|inv| inv * b)`.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let m = Dmat4::from_rows(
+    ///     [2.0, 1.0, 0.0, 0.0],
+    ///     [1.0, 3.0, 1.0, 0.0],
+    ///     [0.0, 1.0, 4.0, 1.0],
+    ///     [0.0, 0.0, 1.0, 5.0],
+    /// );
+    /// let x = Dvec4::new(1.0, -2.0, 3.0, -4.0);
+    /// assert_eq!(m.solve(m * x), Some(x));
+    /// assert_eq!(Dmat4::splat(1.0).solve(x), None);
+    /// ```
+    fn solve(&self, b: V) -> Option<V> {
+        // Augmented matrix, stored row by row
+        let mut rows = [[S::zero(); 5]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, column) in self.columns().enumerate() {
+                row[j] = column[i];
+            }
+            row[4] = b[i];
+        }
+        if !gaussian_elimination(&mut rows) {
+            return None;
+        }
+        Some(V::new(rows[0][4], rows[1][4], rows[2][4], rows[3][4]))
+    }
+
+    /// Inverse of this matrix, or `None` if it is singular (see [`Mat4::solve`]).
+    /// The matrix is factored once and the four columns of the identity are solved together.
+    /// When the matrix is known to be a rotation+translation, [`Mat4::inverse_se3`] is much cheaper.
+    fn inverse(&self) -> Option<Self> {
+        // Matrix augmented with the identity, stored row by row
+        let mut rows = [[S::zero(); 8]; 4];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, column) in self.columns().enumerate() {
+                row[j] = column[i];
+            }
+            row[4 + i] = S::one();
+        }
+        if !gaussian_elimination(&mut rows) {
+            return None;
+        }
+        Some(Self::from_rows(
+            [rows[0][4], rows[0][5], rows[0][6], rows[0][7]],
+            [rows[1][4], rows[1][5], rows[1][6], rows[1][7]],
+            [rows[2][4], rows[2][5], rows[2][6], rows[2][7]],
+            [rows[3][4], rows[3][5], rows[3][6], rows[3][7]],
+        ))
+    }
+
+    /// Treat this matrix as a view-projection matrix and compute the eight corners of the view frustum in world
+    /// space, by transforming the corners of the clip-space cube with the [`Mat4::inverse`], followed by the
+    /// division by `w`. The depth range of the clip-space cube is given by `clip`.
+    /// Returns `None` if the matrix is singular.
+    ///
+    /// The corners are points. The corner `i` is on the right if bit 0 of `i` is set, on the top if bit 1 is set
+    /// and on the far plane if bit 2 is set, so the first four corners are on the near plane.
+    fn frustum_corners(&self, clip: ClipSpace) -> Option<[V; 8]> {
+        let inverse = self.inverse()?;
+        let (one, zero) = (S::one(), S::zero());
+        let (near, far) = match clip {
+            ClipSpace::NegativeOneToOne => (-one, one),
+            ClipSpace::ZeroToOne => (zero, one),
+            ClipSpace::ReversedZ => (one, zero),
+        };
+        Some(std::array::from_fn(|i| {
+            let x = if i & 1 == 0 { -one } else { one };
+            let y = if i & 2 == 0 { -one } else { one };
+            let z = if i & 4 == 0 { near } else { far };
+            let corner = inverse.mul_vector(V::point(x, y, z));
+            corner / corner[3]
+        }))
+    }
+
+    /// Create a transformation that scales, then rotates, then translates.
+    /// The rotation is a unit quaternion stored as `[x, y, z, w]`.
+    /// The fourth components of `scale` and `translation` are ignored.
+    fn from_scale_rotation_translation(scale: V, rotation: V, translation: V) -> Self {
+        let (one, two, zero) = (S::one(), S::one() + S::one(), S::zero());
+        let [x, y, z, w] = *rotation.as_array();
+        let x_axis = V::new(
+            one - two * (y * y + z * z),
+            two * (x * y + w * z),
+            two * (x * z - w * y),
+            zero,
+        );
+        let y_axis = V::new(
+            two * (x * y - w * z),
+            one - two * (x * x + z * z),
+            two * (y * z + w * x),
+            zero,
+        );
+        let z_axis = V::new(
+            two * (x * z + w * y),
+            two * (y * z - w * x),
+            one - two * (x * x + y * y),
+            zero,
+        );
+        let mut t = translation;
+        t[3] = one;
+        Self::from_columns(x_axis * scale[0], y_axis * scale[1], z_axis * scale[2], t)
+    }
+
+    /// Create a rotation+translation matrix from a unit quaternion stored as `[x, y, z, w]` and a translation.
+    /// The fourth component of `translation` is ignored. The result satisfies [`Mat4::is_se3`],
+    /// so it can be inverted with [`Mat4::inverse_se3`].
+    fn from_quat_translation(rotation: V, translation: V) -> Self {
+        Self::from_scale_rotation_translation(V::splat(S::one()), rotation, translation)
+    }
+
+    /// Split a transformation into `(scale, rotation, translation)`, the inverse of
+    /// [`Mat4::from_scale_rotation_translation`]. The rotation is a unit quaternion stored as `[x, y, z, w]`.
+    /// The matrix is assumed to be an affine transformation without shear. Reflections are handled as in
+    /// [`Mat4::extract_scale`].
+    fn decompose(&self) -> (V, V, V) {
+        let (one, half, quarter) = (S::one(), S::from(0.5).unwrap(), S::from(0.25).unwrap());
+        let scale = self.extract_scale();
+        let (c0, c1, c2) = (self[0] / scale[0], self[1] / scale[1], self[2] / scale[2]);
+        let (m00, m01, m02) = (c0[0], c1[0], c2[0]);
+        let (m10, m11, m12) = (c0[1], c1[1], c2[1]);
+        let (m20, m21, m22) = (c0[2], c1[2], c2[2]);
+
+        // Pick the largest of the four candidate divisors to stay well conditioned
+        let trace = m00 + m11 + m22;
+        let rotation = if trace > S::zero() {
+            let s = half / (trace + one).sqrt();
+            V::new(
+                (m21 - m12) * s,
+                (m02 - m20) * s,
+                (m10 - m01) * s,
+                quarter / s,
+            )
+        } else if m00 > m11 && m00 > m22 {
+            let s = half / (one + m00 - m11 - m22).sqrt();
+            V::new(
+                quarter / s,
+                (m01 + m10) * s,
+                (m02 + m20) * s,
+                (m21 - m12) * s,
+            )
+        } else if m11 > m22 {
+            let s = half / (one + m11 - m00 - m22).sqrt();
+            V::new(
+                (m01 + m10) * s,
+                quarter / s,
+                (m12 + m21) * s,
+                (m02 - m20) * s,
+            )
+        } else {
+            let s = half / (one + m22 - m00 - m11).sqrt();
+            V::new(
+                (m02 + m20) * s,
+                (m12 + m21) * s,
+                quarter / s,
+                (m10 - m01) * s,
+            )
+        };
+
+        let mut translation = self[3];
+        translation[3] = S::zero();
+        (scale, rotation, translation)
+    }
+
+    /// Blend two transformations by decomposing them into scale, rotation and translation.
+    /// The scales and translations are interpolated linearly and the rotations spherically,
+    /// so that the result remains a rigid rotation, unlike an elementwise lerp of the matrices.
+    /// Both inputs are assumed to be affine transformations without shear.
+    fn interpolate_trs(&self, rhs: Self, t: S) -> Self {
+        let (s0, q0, t0) = self.decompose();
+        let (s1, mut q1, t1) = rhs.decompose();
+
+        // Take the shortest path
+        let mut cos_theta = q0.dot(q1);
+        if cos_theta < S::zero() {
+            q1 = -q1;
+            cos_theta = -cos_theta;
+        }
+        let rotation = if cos_theta > S::one() - S::epsilon().sqrt() {
+            q0.lerp(q1, t).normalize()
+        } else {
+            let theta = cos_theta.acos();
+            let sin_theta = theta.sin();
+            q0 * (((S::one() - t) * theta).sin() / sin_theta) + q1 * ((t * theta).sin() / sin_theta)
+        };
+
+        Self::from_scale_rotation_translation(s0.lerp(s1, t), rotation, t0.lerp(t1, t))
+    }
+
+    /// Format this matrix on four lines, one per row, like in mathematical notation.
+    /// Unlike [`Debug`](std::fmt::Debug), which prints the columns, this is meant to be read by humans.
+    /// Same as [`Mat4::pretty_prec`] with 4 digits after the decimal point.
+    fn pretty(&self) -> String
+    where
+        S: std::fmt::Display,
+    {
+        self.pretty_prec(4)
+    }
+
+    /// Same as [`Mat4::pretty`], with `precision` digits after the decimal point.
+    /// The elements are right-aligned to the width of the longest one.
+    ///
+    /// ```
+    /// use mafs::{Mat4, Dmat4, Vec4, Dvec4};
+    ///
+    /// let m = Dmat4::from_translation(Dvec4::new(-12.0, 3.5, 0.0, 0.0));
+    /// assert_eq!(m.pretty_prec(1), concat!(
+    ///     "  1.0    0.0    0.0  -12.0\n",
+    ///     "  0.0    1.0    0.0    3.5\n",
+    ///     "  0.0    0.0    1.0    0.0\n",
+    ///     "  0.0    0.0    0.0    1.0",
+    /// ));
+    /// ```
+    fn pretty_prec(&self, precision: usize) -> String
+    where
+        S: std::fmt::Display,
+    {
+        let rows = self.transpose();
+        let elements: Vec<String> = rows
+            .elements()
+            .map(|x| format!("{x:.precision$}"))
+            .collect();
+        let width = elements.iter().map(String::len).max().unwrap_or(0);
+        let lines: Vec<String> = elements
+            .chunks(4)
+            .map(|row| {
+                let row: Vec<String> = row.iter().map(|x| format!("{x:>width$}")).collect();
+                row.join("  ")
+            })
+            .collect();
+        lines.join("\n")
+    }
+}
+
+/// Binary exponent of `x`, i.e. `floor(log2(|x|))`, or `i32::MIN` for zero. See [`Vec4::ilog2`].
+fn exponent<S: Float>(x: S) -> i32 {
+    let (mantissa, exponent, _) = x.integer_decode();
+    if mantissa == 0 {
+        i32::MIN
+    } else {
+        i32::from(exponent) + 63 - mantissa.leading_zeros() as i32
+    }
+}
+
+/// Solve the linear systems whose augmented matrix is `rows`: the first four columns are the matrix and the
+/// others are the right-hand sides, which are replaced by the solutions. The matrix is reduced once, with partial
+/// pivoting. Returns false if the matrix is singular, in which case `rows` is left in an unspecified state.
+fn gaussian_elimination<S: Float, const N: usize>(rows: &mut [[S; N]; 4]) -> bool {
+    let largest = rows
+        .iter()
+        .flat_map(|row| &row[..4])
+        .fold(S::zero(), |acc, x| acc.max(x.abs()));
+    let tolerance = largest * S::epsilon() * S::from(4.0).unwrap();
+
+    for k in 0..4 {
+        // Bring the largest remaining element of column k on the diagonal
+        let mut pivot = k;
+        for i in k + 1..4 {
+            if rows[i][k].abs() > rows[pivot][k].abs() {
+                pivot = i;
+            }
+        }
+        if rows[pivot][k].abs() <= tolerance {
+            return false;
+        }
+        rows.swap(k, pivot);
+        let pivot_row = rows[k];
+        for row in &mut rows[k + 1..] {
+            let factor = row[k] / pivot_row[k];
+            for j in k..N {
+                row[j] = row[j] - factor * pivot_row[j];
+            }
+        }
+    }
+
+    // Back substitution, for each right-hand side
+    for c in 4..N {
+        for i in (0..4).rev() {
+            let sum = (i + 1..4).fold(rows[i][c], |sum, j| sum - rows[i][j] * rows[j][c]);
+            rows[i][c] = sum / rows[i][i];
+        }
+    }
+    true
 }